@@ -0,0 +1,66 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{deserialize_duration, duration_to_config_str, Alarm, AlarmScheduler, NeedleErr};
+use serde::Deserialize;
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AlarmConfig {
+    #[serde(
+        default = "AlarmConfig::default_snooze",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub snooze: Duration,
+    #[serde(default)]
+    pub alarms: Vec<Alarm>,
+}
+
+impl AlarmConfig {
+    fn default_snooze() -> Duration {
+        AlarmScheduler::DEFAULT_SNOOZE
+    }
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        self.alarms.iter().try_for_each(|alarm| alarm.validate())
+    }
+}
+
+impl Default for AlarmConfig {
+    fn default() -> Self {
+        Self {
+            snooze: Self::default_snooze(),
+            alarms: vec![],
+        }
+    }
+}
+
+impl Display for AlarmConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Length of a snooze")?;
+        writeln!(f, "#  \"5m\" (default)")?;
+        writeln!(f, "snooze = \"{}\"", duration_to_config_str(&self.snooze))?;
+        writeln!(f, "# Alarms fired in the timezone of the clock")?;
+        writeln!(f, "#  [[alarm.alarms]]")?;
+        writeln!(f, "#  label = \"Wake up\"")?;
+        writeln!(f, "#  time = \"07:30\"")?;
+        writeln!(f, "#  repeat : When the alarm fires")?;
+        writeln!(f, "#   {{ Once = \"2026-12-01\" }}")?;
+        writeln!(f, "#   \"Daily\"")?;
+        writeln!(f, "#   {{ Weekdays = [\"Mon\", \"Wed\", \"Fri\"] }}")?;
+        writeln!(f, "#   {{ Dates = [\"2026-12-24\", \"2026-12-31\"] }}")?;
+        writeln!(
+            f,
+            "#   {{ Every = {{ minutes = 30, until = \"17:00\" }} }} : Until is optional"
+        )?;
+        write!(f, "#  enabled = true (default)")?;
+        for alarm in &self.alarms {
+            write!(f, "\n\n[[alarm.alarms]]\n{}", alarm)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{Position, Text};
+use crate::{deserialize_duration, duration_to_config_str, ChessClock, ChessIncrement};
+use serde::Deserialize;
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ChessConfig {
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub time: Duration,
+    #[serde(default)]
+    pub increment: ChessIncrement,
+    #[serde(default = "ChessConfig::default_white")]
+    pub white: Text,
+    #[serde(default = "ChessConfig::default_black")]
+    pub black: Text,
+}
+
+impl ChessConfig {
+    pub fn chess_clock(&self) -> ChessClock {
+        ChessClock {
+            time: self.time,
+            increment: self.increment,
+        }
+    }
+
+    fn default_white() -> Text {
+        Self::default().white
+    }
+
+    fn default_black() -> Text {
+        Self::default().black
+    }
+
+    fn write_text(f: &mut Formatter<'_>, prefix: &str, text: &Text) -> fmt::Result {
+        for line in format!("{}", text).lines() {
+            if line.starts_with("#") {
+                writeln!(f, "{}", line)?;
+            } else {
+                writeln!(f, "{}.{}", prefix, line)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ChessConfig {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(5 * 60),
+            increment: ChessIncrement::None,
+            white: Text {
+                scale: 0.5,
+                color: [255, 255, 255, 255],
+                position: Position::Left,
+            },
+            black: Text {
+                scale: 0.5,
+                color: [255, 255, 255, 255],
+                position: Position::Right,
+            },
+        }
+    }
+}
+
+impl Display for ChessConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Initial time of each side")?;
+        writeln!(f, "#  e.g. \"5m\", \"1h30m\", \"00:25:00\", \"PT25M\"")?;
+        writeln!(f, "time = \"{}\"", duration_to_config_str(&self.time))?;
+        writeln!(f, "# Time control applied on every move")?;
+        writeln!(f, "#  \"None\" (default)")?;
+        writeln!(
+            f,
+            "#  {{ Fischer = \"<duration>\" }} : Added after each move"
+        )?;
+        writeln!(
+            f,
+            "#  {{ Bronstein = \"<duration>\" }} : Time used is given back up to the delay"
+        )?;
+        match self.increment {
+            ChessIncrement::None => writeln!(f, "increment = \"None\""),
+            ChessIncrement::Fischer(increment) => writeln!(
+                f,
+                "increment = {{ Fischer = \"{}\" }}",
+                duration_to_config_str(&increment)
+            ),
+            ChessIncrement::Bronstein(delay) => writeln!(
+                f,
+                "increment = {{ Bronstein = \"{}\" }}",
+                duration_to_config_str(&delay)
+            ),
+        }?;
+        writeln!(f, "# Text of white")?;
+        Self::write_text(f, "white", &self.white)?;
+        writeln!(f, "# Text of black")?;
+        Self::write_text(f, "black", &self.black)
+    }
+}
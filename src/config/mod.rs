@@ -1,22 +1,30 @@
 // Copyright 2025 Kensuke Saito
 // SPDX-License-Identifier: GPL-2.0-or-later
 
+mod alarm;
+mod chess;
 mod fps;
+mod pomodoro;
 mod position;
+mod solar;
 mod text;
 mod time;
 
+pub use alarm::*;
+pub use chess::*;
 pub use fps::*;
+pub use pomodoro::*;
 pub use position::*;
+pub use solar::*;
 pub use text::*;
 pub use time::*;
 
 use crate::{
     error::{NeedleErr, NeedleError},
-    TimeFormat,
+    DurationFormat, OpMode, Representation, SuspendPolicy, TimeFormat, Zone,
 };
 use directories::ProjectDirs;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 use std::{
     ffi::OsStr,
     fmt::{self, Display, Formatter},
@@ -30,6 +38,14 @@ pub struct NeedleConfig {
     pub background_color: [f32; 4],
     pub time: TimeConfig,
     pub fps: FpsConfig,
+    #[serde(default)]
+    pub pomodoro: PomodoroConfig,
+    #[serde(default)]
+    pub chess: ChessConfig,
+    #[serde(default)]
+    pub solar: SolarConfig,
+    #[serde(default)]
+    pub alarm: AlarmConfig,
 }
 
 impl<'a> NeedleConfig {
@@ -76,38 +92,67 @@ impl<'a> NeedleConfig {
             }
         }
 
-        let read = match OpenOptions::new().read(true).open(config_file) {
-            Ok(file) => Ok(file),
-            Err(err) => Err(NeedleError::FailedToOpenConfig(err.into())),
-        }?;
-        let mut buf_reader = BufReader::new(read);
-        let mut read_buffer = String::new();
+        let mut config: Self = Self::read(config_file)?;
 
-        match buf_reader.read_to_string(&mut read_buffer) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(NeedleError::FailedToReadConfig(err.into())),
-        }?;
+        // Blank strings in the config file mean unset
+        for field in [
+            &mut config.time.font,
+            &mut config.time.target,
+            &mut config.time.cron,
+            &mut config.time.calendar,
+        ] {
+            if field.as_deref().is_some_and(str::is_empty) {
+                *field = None;
+            }
+        }
 
-        let mut config: Self = match toml::from_str(&read_buffer) {
-            Ok(toml) => Ok(toml),
-            Err(err) => Err(NeedleError::FailedToParseConfig(err.into())),
-        }?;
+        config.time.format.validate()?;
+        config.time.target()?;
+        config.time.cron()?;
 
-        if let Some(ref font) = config.time.font {
-            if font.is_empty() {
-                config.time.font = None;
-            }
+        // Parsed by `TimeConfig::calendar` once the calendar is used
+        if let Some(ref calendar) = config.time.calendar {
+            match fs::metadata(calendar) {
+                Ok(_) => Ok(()),
+                Err(err) => Err(NeedleError::FailedToReadCalendar(err.into())),
+            }?;
         }
 
+        config.pomodoro.pomodoro().validate()?;
+        config.solar.validate()?;
+        config.alarm.validate()?;
+
         if config.fps.enable && !config.fps.is_valid_position() {
-            Err(NeedleError::InvalidFpsTextPosition(
+            return Err(NeedleError::InvalidFpsTextPosition(
                 config.fps.config.position,
-            ))
-        } else if config.fps.enable && (config.fps.config.position == config.time.config.position) {
-            Err(NeedleError::TextPositionOverlapping)
-        } else {
-            Ok(config)
+            ));
         }
+
+        config.validate_positions(&OpMode::Clock)?;
+
+        Ok(config)
+    }
+
+    /// Checks that none of the texts drawn in `mode` share a position: the
+    /// time, the fps counter when enabled and both sides of a chess clock.
+    pub fn validate_positions(&self, mode: &OpMode) -> NeedleErr<()> {
+        let mut positions = vec![self.time.config.position];
+
+        if self.fps.enable {
+            positions.push(self.fps.config.position);
+        }
+        if let OpMode::ChessClock(_) = mode {
+            positions.push(self.chess.white.position);
+            positions.push(self.chess.black.position);
+        }
+
+        for (i, position) in positions.iter().enumerate() {
+            if positions[i + 1..].contains(position) {
+                return Err(NeedleError::TextPositionOverlapping);
+            }
+        }
+
+        Ok(())
     }
 
     pub fn config_path(create_dir: bool, relative_path: Option<&str>) -> NeedleErr<PathBuf> {
@@ -165,6 +210,26 @@ impl<'a> NeedleConfig {
         }
     }
 
+    /// Reads and parses a TOML file under the same rules as `config.toml`
+    pub(crate) fn read<T: DeserializeOwned>(file: &Path) -> NeedleErr<T> {
+        let read = match OpenOptions::new().read(true).open(file) {
+            Ok(file) => Ok(file),
+            Err(err) => Err(NeedleError::FailedToOpenConfig(err.into())),
+        }?;
+        let mut buf_reader = BufReader::new(read);
+        let mut read_buffer = String::new();
+
+        match buf_reader.read_to_string(&mut read_buffer) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(NeedleError::FailedToReadConfig(err.into())),
+        }?;
+
+        match toml::from_str(&read_buffer) {
+            Ok(toml) => Ok(toml),
+            Err(err) => Err(NeedleError::FailedToParseConfig(err.into())),
+        }
+    }
+
     fn config_file(create_dir: bool) -> NeedleErr<PathBuf> {
         Self::config_path(create_dir, Some(Self::CONFIG_FILE))
     }
@@ -207,6 +272,16 @@ impl Default for NeedleConfig {
             background_color: [0.0, 0.0, 0.0, 1.0],
             time: TimeConfig {
                 format: TimeFormat::HourMinSec,
+                representation: Representation::Clock,
+                duration: DurationFormat::default(),
+                timezone: Zone::Local,
+                world_clock: vec![],
+                target: None,
+                cron: None,
+                calendar: None,
+                overtime: false,
+                overtime_prefix: TimeConfig::default_overtime_prefix(),
+                suspend: SuspendPolicy::default(),
                 font: None,
                 config: Text {
                     scale: 1.0,
@@ -223,6 +298,10 @@ impl Default for NeedleConfig {
                     position: Position::TopRight,
                 },
             },
+            pomodoro: PomodoroConfig::default(),
+            chess: ChessConfig::default(),
+            solar: SolarConfig::default(),
+            alarm: AlarmConfig::default(),
         }
     }
 }
@@ -242,6 +321,407 @@ impl Display for NeedleConfig {
         writeln!(f, "{}[time]", Self::NEWLINE)?;
         writeln!(f, "{}", self.time)?;
         writeln!(f, "{}[fps]", Self::NEWLINE)?;
-        write!(f, "{}", self.fps)
+        writeln!(f, "{}", self.fps)?;
+        writeln!(f, "{}[pomodoro]", Self::NEWLINE)?;
+        writeln!(f, "{}", self.pomodoro)?;
+        writeln!(f, "{}[chess]", Self::NEWLINE)?;
+        writeln!(f, "{}", self.chess)?;
+        writeln!(f, "{}[solar]", Self::NEWLINE)?;
+        writeln!(f, "{}", self.solar)?;
+        writeln!(f, "{}[alarm]", Self::NEWLINE)?;
+        write!(f, "{}", self.alarm)
+    }
+}
+
+#[test]
+fn test_config_0001() {
+    let mut config = NeedleConfig::default();
+
+    config.time.format = TimeFormat::Custom("%a \"%-I:%M\" %p".to_string());
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.time.format, parsed.time.format)
+}
+
+#[test]
+fn test_config_0002() {
+    let mut config = NeedleConfig::default();
+
+    config.time.timezone = Zone::parse("+09:00").unwrap();
+    config.time.world_clock = vec![
+        crate::WorldClock {
+            label: "New \"York\"".to_string(),
+            timezone: Zone::parse("America/New_York").unwrap(),
+        },
+        crate::WorldClock {
+            label: "Local".to_string(),
+            timezone: Zone::Local,
+        },
+    ];
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.time.timezone, parsed.time.timezone);
+    assert_eq!(config.time.world_clock, parsed.time.world_clock)
+}
+
+#[test]
+fn test_config_0003() {
+    let config = NeedleConfig::default().to_string();
+    let config = config.replace("timezone = \"Local\"", "timezone = \"Nowhere/City\"");
+
+    assert!(toml::from_str::<NeedleConfig>(&config).is_err())
+}
+
+#[test]
+fn test_config_0004() {
+    let mut config = NeedleConfig::default();
+
+    config.time.timezone = Zone::parse("Asia/Tokyo").unwrap();
+    config.time.target = Some("2026-12-01T09:00".to_string());
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(
+        Some(chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 12, 1, 0, 0, 0).unwrap()),
+        parsed.time.target().unwrap()
+    );
+
+    config.time.target = Some("next tuesday".to_string());
+    assert!(config.time.target().is_err())
+}
+
+#[test]
+fn test_config_0005() {
+    let config = NeedleConfig {
+        pomodoro: PomodoroConfig {
+            work: std::time::Duration::from_secs(50 * 60),
+            short_break: std::time::Duration::from_secs(10 * 60),
+            long_break: std::time::Duration::from_millis(90_500),
+            cycles: 3,
+        },
+        ..Default::default()
+    };
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.pomodoro, parsed.pomodoro);
+    assert_eq!(
+        std::time::Duration::from_secs(50 * 60),
+        parsed.pomodoro.pomodoro().work
+    );
+
+    // Bare numbers are minutes
+    let parsed: PomodoroConfig =
+        toml::from_str("work = 25\nshort_break = \"5m\"\nlong_break = \"PT15M\"\ncycles = 4")
+            .unwrap();
+
+    assert_eq!(PomodoroConfig::default(), parsed)
+}
+
+#[test]
+fn test_config_0006() {
+    let mut config = NeedleConfig::default();
+
+    config.time.overtime = true;
+    config.time.overtime_prefix = "OVER +".to_string();
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert!(parsed.time.overtime);
+    assert_eq!("OVER +", parsed.time.overtime_prefix)
+}
+
+#[test]
+fn test_config_0007() {
+    let mut config = NeedleConfig::default();
+
+    config.time.duration = DurationFormat {
+        days: true,
+        trim: true,
+    };
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.time.duration, parsed.time.duration)
+}
+
+#[test]
+fn test_config_0008() {
+    let mut config = NeedleConfig::default();
+
+    config.chess.time = std::time::Duration::from_millis(90_500);
+    config.chess.increment = crate::ChessIncrement::Bronstein(std::time::Duration::from_secs(3));
+    config.chess.black.position = Position::BottomRight;
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.chess.chess_clock(), parsed.chess.chess_clock());
+    assert_eq!(Position::Left, parsed.chess.white.position);
+    assert_eq!(Position::BottomRight, parsed.chess.black.position);
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string().replace(
+        "increment = { Bronstein = \"3s\" }",
+        "increment = { Fischer = \"PT1.5S\" }",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        crate::ChessIncrement::Fischer(std::time::Duration::from_millis(1_500)),
+        parsed.chess.increment
+    )
+}
+
+#[test]
+fn test_config_0009() {
+    let mut config = NeedleConfig::default();
+
+    for rate in [
+        crate::FrameRate::Fps23_976,
+        crate::FrameRate::Fps29_97DropFrame,
+    ] {
+        config.time.format = TimeFormat::Timecode(rate);
+
+        let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+        assert_eq!(config.time.format, parsed.time.format)
     }
 }
+
+#[test]
+fn test_config_0010() {
+    let mut config = NeedleConfig::default();
+
+    config.time.representation = Representation::DecimalTime;
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(Representation::DecimalTime, parsed.time.representation);
+
+    let parsed: NeedleConfig = toml::from_str(
+        &config
+            .to_string()
+            .replace("representation = \"DecimalTime\"\n", ""),
+    )
+    .unwrap();
+
+    assert_eq!(Representation::Clock, parsed.time.representation);
+
+    config.time.representation = Representation::Words {
+        locale: crate::Locale::Japanese,
+        fuzziness: crate::Fuzziness::QuarterHour,
+    };
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.time.representation, parsed.time.representation);
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string().replace(
+        "representation = { Words = { locale = \"Japanese\", fuzziness = \"QuarterHour\" } }",
+        "representation = { Words = {} }",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        Representation::Words {
+            locale: crate::Locale::English,
+            fuzziness: crate::Fuzziness::Exact,
+        },
+        parsed.time.representation
+    )
+}
+
+#[test]
+fn test_config_0011() {
+    let mut config = NeedleConfig::default();
+
+    config.alarm.snooze = std::time::Duration::from_secs(90);
+    config.alarm.alarms = vec![
+        crate::Alarm {
+            label: "Wake \"up\"".to_string(),
+            time: "07:30".parse().unwrap(),
+            repeat: crate::Recurrence::Weekdays(vec![chrono::Weekday::Mon, chrono::Weekday::Fri]),
+            enabled: true,
+        },
+        crate::Alarm {
+            label: "Stretch".to_string(),
+            time: "09:00".parse().unwrap(),
+            repeat: crate::Recurrence::Every {
+                minutes: 45,
+                until: Some("17:00".parse().unwrap()),
+            },
+            enabled: false,
+        },
+        crate::Alarm {
+            label: "Release".to_string(),
+            time: "12:00:30".parse().unwrap(),
+            repeat: crate::Recurrence::Once("2026-12-01".parse().unwrap()),
+            enabled: true,
+        },
+    ];
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.alarm, parsed.alarm);
+    assert_eq!(
+        AlarmConfig::default(),
+        toml::from_str::<NeedleConfig>(&NeedleConfig::default().to_string())
+            .unwrap()
+            .alarm
+    )
+}
+
+#[test]
+fn test_config_0012() {
+    let mut config = NeedleConfig::default();
+
+    config.time.timezone = Zone::parse("Asia/Tokyo").unwrap();
+    config.time.cron = Some("30 9 * * 1-5".to_string());
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+    let schedule = parsed.time.cron().unwrap().unwrap();
+
+    assert_eq!("30 9 * * 1-5", schedule.expression());
+    assert_eq!(config.time.timezone, schedule.timezone());
+
+    config.time.cron = Some("30 9 * *".to_string());
+    assert!(config.time.cron().is_err())
+}
+
+#[test]
+fn test_config_0013() {
+    let path = std::env::temp_dir().join("needle_test_config_0013.ics");
+    let mut config = NeedleConfig::default();
+
+    fs::write(
+        &path,
+        "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:a\r\nSUMMARY:Deploy\r\n\
+         DTSTART:20260101T090000\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+    )
+    .unwrap();
+
+    config.time.timezone = Zone::parse("+09:00").unwrap();
+    config.time.calendar = Some(path.to_string_lossy().to_string());
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+    let calendar = parsed.time.calendar().unwrap().unwrap();
+
+    assert_eq!(path.as_path(), calendar.path());
+    assert_eq!(
+        Some(chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2026, 1, 1, 0, 0, 0).unwrap()),
+        calendar
+            .calendar()
+            .next_event(&chrono::DateTime::UNIX_EPOCH)
+            .map(|event| event.start)
+    );
+
+    fs::remove_file(&path).unwrap();
+    assert!(config.time.calendar().is_err())
+}
+
+#[test]
+fn test_config_0014() {
+    let mut config = NeedleConfig {
+        solar: SolarConfig {
+            latitude: -33.8688,
+            longitude: 151.2093,
+        },
+        ..Default::default()
+    };
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(config.solar, parsed.solar);
+    assert_eq!(
+        SolarConfig::default(),
+        toml::from_str::<NeedleConfig>(&NeedleConfig::default().to_string())
+            .unwrap()
+            .solar
+    );
+
+    config.solar.latitude = 91.0;
+    assert!(config.solar.validate().is_err())
+}
+
+#[test]
+fn test_config_0015() {
+    let mut config = NeedleConfig::default();
+
+    config.time.suspend = SuspendPolicy::Include;
+
+    let parsed: NeedleConfig = toml::from_str(&config.to_string()).unwrap();
+
+    assert_eq!(SuspendPolicy::Include, parsed.time.suspend);
+    assert_eq!(
+        SuspendPolicy::Exclude,
+        toml::from_str::<NeedleConfig>(&NeedleConfig::default().to_string())
+            .unwrap()
+            .time
+            .suspend
+    )
+}
+
+#[test]
+fn test_config_0016() {
+    let config = NeedleConfig::default()
+        .to_string()
+        .lines()
+        .filter(|line| !(line.starts_with("white.") || line.starts_with("black.")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut parsed: NeedleConfig = toml::from_str(&config).unwrap();
+    let chess = OpMode::ChessClock(parsed.chess.chess_clock());
+
+    assert_eq!(Position::Left, parsed.chess.white.position);
+    assert_eq!(Position::Right, parsed.chess.black.position);
+    assert!(parsed.validate_positions(&chess).is_ok());
+
+    // Chess texts only matter while the chess clock is shown
+    parsed.chess.white.position = parsed.time.config.position;
+    assert!(parsed.validate_positions(&OpMode::Clock).is_ok());
+    assert!(matches!(
+        parsed.validate_positions(&chess),
+        Err(NeedleError::TextPositionOverlapping)
+    ));
+
+    parsed.chess.white.position = Position::Left;
+    parsed.fps.enable = true;
+    parsed.chess.black.position = parsed.fps.config.position;
+    assert!(matches!(
+        parsed.validate_positions(&chess),
+        Err(NeedleError::TextPositionOverlapping)
+    ))
+}
+
+#[test]
+fn test_config_0017() {
+    let dir = std::env::temp_dir();
+    let path = dir.join("needle_test_config_0017.toml");
+    let calendar = dir.join("needle_test_config_0017.ics");
+    let mut config = NeedleConfig::default();
+
+    config.time.calendar = Some(calendar.to_string_lossy().into());
+
+    // Only read once the calendar is used
+    fs::write(&calendar, "not a calendar").unwrap();
+    fs::write(&path, config.to_string()).unwrap();
+
+    let parsed = NeedleConfig::from(path.to_str()).unwrap();
+
+    assert_eq!(None, parsed.time.font);
+    assert_eq!(None, parsed.time.target);
+    assert_eq!(None, parsed.time.cron);
+    assert_eq!(config.time.calendar, parsed.time.calendar);
+
+    fs::remove_file(&calendar).unwrap();
+    assert!(matches!(
+        NeedleConfig::from(path.to_str()),
+        Err(NeedleError::FailedToReadCalendar(_))
+    ));
+
+    config.time.calendar = None;
+    fs::write(&path, config.to_string()).unwrap();
+    assert!(NeedleConfig::from(path.to_str()).is_ok());
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,70 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{deserialize_minutes, duration_to_config_str, Pomodoro};
+use serde::Deserialize;
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct PomodoroConfig {
+    #[serde(deserialize_with = "deserialize_minutes")]
+    pub work: Duration,
+    #[serde(deserialize_with = "deserialize_minutes")]
+    pub short_break: Duration,
+    #[serde(deserialize_with = "deserialize_minutes")]
+    pub long_break: Duration,
+    pub cycles: u32,
+}
+
+impl PomodoroConfig {
+    const MINUTE_SECS: u64 = 60;
+
+    pub fn pomodoro(&self) -> Pomodoro {
+        Pomodoro {
+            work: self.work,
+            short_break: self.short_break,
+            long_break: self.long_break,
+            cycles: self.cycles,
+        }
+    }
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work: Duration::from_secs(25 * Self::MINUTE_SECS),
+            short_break: Duration::from_secs(5 * Self::MINUTE_SECS),
+            long_break: Duration::from_secs(15 * Self::MINUTE_SECS),
+            cycles: 4,
+        }
+    }
+}
+
+impl Display for PomodoroConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Length of a work phase, bare numbers being minutes")?;
+        writeln!(f, "#  e.g. \"25m\", \"1h30m\", \"00:25:00\", \"PT25M\", 25")?;
+        writeln!(f, "#  \"1500s\" (default)")?;
+        writeln!(f, "work = \"{}\"", duration_to_config_str(&self.work))?;
+        writeln!(f, "# Length of a short break")?;
+        writeln!(f, "#  \"300s\" (default)")?;
+        writeln!(
+            f,
+            "short_break = \"{}\"",
+            duration_to_config_str(&self.short_break)
+        )?;
+        writeln!(f, "# Length of a long break")?;
+        writeln!(f, "#  \"900s\" (default)")?;
+        writeln!(
+            f,
+            "long_break = \"{}\"",
+            duration_to_config_str(&self.long_break)
+        )?;
+        writeln!(f, "# Number of work phases before a long break")?;
+        writeln!(f, "#  4 (default)")?;
+        write!(f, "cycles = {}", self.cycles)
+    }
+}
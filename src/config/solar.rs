@@ -0,0 +1,42 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{Coordinates, NeedleErr};
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub struct SolarConfig {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl SolarConfig {
+    pub fn coordinates(&self) -> Coordinates {
+        Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        self.coordinates().validate()
+    }
+}
+
+impl Display for SolarConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Location sunrise, sunset and twilight are calculated for"
+        )?;
+        writeln!(f, "# Latitude in degrees, north is positive")?;
+        writeln!(f, "#  Range : (-90.0 - 90.0)")?;
+        writeln!(f, "#  Example: 35.6812")?;
+        writeln!(f, "latitude = {:?}", self.latitude)?;
+        writeln!(f, "# Longitude in degrees, east is positive")?;
+        writeln!(f, "#  Range : (-180.0 - 180.0)")?;
+        writeln!(f, "#  Example: 139.7671")?;
+        write!(f, "longitude = {:?}", self.longitude)
+    }
+}
@@ -2,17 +2,74 @@
 // SPDX-License-Identifier: GPL-2.0-or-later
 
 use super::Text;
-use crate::TimeFormat;
+use crate::{
+    CalendarSource, CronSchedule, DurationFormat, NeedleErr, Representation, SuspendPolicy,
+    TimeFormat, WorldClock, Zone,
+};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct TimeConfig {
     pub format: TimeFormat,
+    #[serde(default)]
+    pub representation: Representation,
+    #[serde(default)]
+    pub duration: DurationFormat,
+    #[serde(default)]
+    pub timezone: Zone,
+    #[serde(default)]
+    pub world_clock: Vec<WorldClock>,
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub cron: Option<String>,
+    #[serde(default)]
+    pub calendar: Option<String>,
+    #[serde(default)]
+    pub overtime: bool,
+    #[serde(default = "TimeConfig::default_overtime_prefix")]
+    pub overtime_prefix: String,
+    #[serde(default)]
+    pub suspend: SuspendPolicy,
     pub font: Option<String>,
     pub config: Text,
 }
 
+impl TimeConfig {
+    pub(crate) fn default_overtime_prefix() -> String {
+        "-".to_string()
+    }
+
+    /// Countdown target resolved in `timezone` unless it has an explicit offset
+    pub fn target(&self) -> NeedleErr<Option<DateTime<Utc>>> {
+        match self.target {
+            Some(ref target) => self.timezone.parse_datetime(target).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Cron countdown schedule evaluated in `timezone`
+    pub fn cron(&self) -> NeedleErr<Option<CronSchedule>> {
+        match self.cron {
+            Some(ref expression) => CronSchedule::parse(expression, self.timezone).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// iCalendar file with floating times read in `timezone`
+    pub fn calendar(&self) -> NeedleErr<Option<CalendarSource>> {
+        match self.calendar {
+            Some(ref path) => CalendarSource::new(Path::new(path), self.timezone).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
 impl Display for TimeConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let config = format!("{}", self.config);
@@ -21,7 +78,194 @@ impl Display for TimeConfig {
         writeln!(f, "# Time format")?;
         writeln!(f, "#  HourMinSec : HH:MM:SS (default)")?;
         writeln!(f, "#  HourMinSecMSec : HH:MM:SS.MSec")?;
-        writeln!(f, "format = \"{}\"", self.format)?;
+        writeln!(f, "#  {{ Custom = \"<pattern>\" }} : User defined pattern")?;
+        writeln!(
+            f,
+            "#   %H : Hour (00-23)     %I : Hour (01-12)   %p : AM/PM"
+        )?;
+        writeln!(f, "#   %M : Minute (00-59)   %S : Second (00-59)")?;
+        writeln!(f, "#   %1f, %2f, %3f : Tenths, hundredths, milliseconds")?;
+        writeln!(f, "#   %a, %A : Weekday (Mon, Monday)")?;
+        writeln!(f, "#   %Y, %y, %m, %d : Year, short year, month, day")?;
+        writeln!(f, "#   %b, %B : Month name (Jan, January)")?;
+        writeln!(f, "#   %D : Days (countup/countdown timer only)")?;
+        writeln!(f, "#   %O : Overtime prefix (countdown timer only)")?;
+        writeln!(f, "#   %% : Literal \"%\"")?;
+        writeln!(f, "#   \"%-H\" disables padding, \"%_H\" pads with spaces")?;
+        writeln!(f, "#  Example:")?;
+        writeln!(f, "#      format = {{ Custom = \"%a %-I:%M %p\" }}")?;
+        writeln!(
+            f,
+            "#  {{ Timecode = \"<frame rate>\" }} : SMPTE timecode HH:MM:SS:FF"
+        )?;
+        writeln!(
+            f,
+            "#   \"23.976\", \"24\", \"25\", \"29.97DF\", \"30\", \"50\", \"60\""
+        )?;
+        writeln!(f, "#   29.97DF is drop-frame, rendered as HH:MM:SS;FF")?;
+        match &self.format {
+            TimeFormat::Custom(pattern) => writeln!(
+                f,
+                "format = {{ Custom = {} }}",
+                toml::Value::String(pattern.clone())
+            ),
+            TimeFormat::Timecode(rate) => writeln!(f, "format = {{ Timecode = \"{}\" }}", rate),
+            format => writeln!(f, "format = \"{}\"", format),
+        }?;
+        writeln!(f, "# Representation of the time of day (clock modes only)")?;
+        writeln!(f, "#  Clock (default) : Time format above")?;
+        writeln!(f, "#  UnixSeconds : Seconds since 1970-01-01T00:00:00Z")?;
+        writeln!(f, "#  UnixMillis : Milliseconds since 1970-01-01T00:00:00Z")?;
+        writeln!(f, "#  Iso8601 : e.g. \"2025-01-01T21:34:56+09:00\"")?;
+        writeln!(f, "#  JulianDay : Julian day with fraction")?;
+        writeln!(
+            f,
+            "#  DecimalTime : French decimal time (10 hours a day, 100 minutes an hour)"
+        )?;
+        writeln!(f, "#  Binary : Hour, minute and second in binary")?;
+        writeln!(f, "#  Hex : Hour, minute and second in hexadecimal")?;
+        writeln!(
+            f,
+            "#  {{ Words = {{ locale, fuzziness }} }} : Time in words"
+        )?;
+        writeln!(f, "#   locale : English (default), Japanese")?;
+        writeln!(
+            f,
+            "#   fuzziness : Exact (default), FiveMinutes, QuarterHour"
+        )?;
+        writeln!(f, "#  Example:")?;
+        writeln!(
+            f,
+            "#      representation = {{ Words = {{ locale = \"English\", fuzziness = \"FiveMinutes\" }} }}"
+        )?;
+        match self.representation {
+            Representation::Words { locale, fuzziness } => writeln!(
+                f,
+                "representation = {{ Words = {{ locale = \"{}\", fuzziness = \"{}\" }} }}",
+                locale, fuzziness
+            ),
+            representation => writeln!(f, "representation = \"{}\"", representation),
+        }?;
+        writeln!(
+            f,
+            "# Layout of countup/countdown timers (HourMinSec, HourMinSecMSec)"
+        )?;
+        writeln!(
+            f,
+            "#  days : Roll 24 hours over into days, e.g. \"1d 02:05:03\""
+        )?;
+        writeln!(
+            f,
+            "#  trim : Omit zero leading fields, e.g. \"5:03\" instead of \"00:05:03\""
+        )?;
+        writeln!(f, "#  false (default)")?;
+        writeln!(
+            f,
+            "duration.days = {}",
+            if self.duration.days { "true" } else { "false" }
+        )?;
+        writeln!(
+            f,
+            "duration.trim = {}",
+            if self.duration.trim { "true" } else { "false" }
+        )?;
+        writeln!(f, "# Timezone of the clock")?;
+        writeln!(f, "#  Local (default) : Timezone of the system")?;
+        writeln!(f, "#  IANA timezone name : e.g. \"Asia/Tokyo\", \"UTC\"")?;
+        writeln!(f, "#  UTC offset : e.g. \"+09:00\", \"-05:30\"")?;
+        writeln!(f, "timezone = \"{}\"", self.timezone)?;
+        writeln!(f, "# Labelled timezones rendered in world clock mode")?;
+        writeln!(f, "#  Example:")?;
+        writeln!(
+            f,
+            "#      world_clock = [{{ label = \"Tokyo\", timezone = \"Asia/Tokyo\" }}]"
+        )?;
+        writeln!(
+            f,
+            "world_clock = [{}]",
+            self.world_clock
+                .iter()
+                .map(|entry| entry.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(f, "# Countdown target (Optional)")?;
+        writeln!(
+            f,
+            "#  Interpreted in the timezone above unless an offset is given."
+        )?;
+        writeln!(f, "#  target = \"\" (default)")?;
+        writeln!(f, "#  Example:")?;
+        writeln!(f, "#      target = \"2026-12-01T09:00\"")?;
+        writeln!(f, "#      target = \"2026-12-01T09:00:00+09:00\"")?;
+        match &self.target {
+            Some(target) => writeln!(f, "target = {}", toml::Value::String(target.clone())),
+            None => writeln!(f, "target = \"\""),
+        }?;
+        writeln!(
+            f,
+            "# Cron expression counted down to in cron mode (Optional)"
+        )?;
+        writeln!(
+            f,
+            "#  minute hour day month weekday, with optional leading seconds."
+        )?;
+        writeln!(
+            f,
+            "#  Evaluated in the timezone above unless prefixed with CRON_TZ=<timezone>."
+        )?;
+        writeln!(f, "#  cron = \"\" (default)")?;
+        writeln!(f, "#  Example:")?;
+        writeln!(f, "#      cron = \"0 9 * * 1-5\"")?;
+        writeln!(f, "#      cron = \"CRON_TZ=UTC */30 * * * *\"")?;
+        match &self.cron {
+            Some(cron) => writeln!(f, "cron = {}", toml::Value::String(cron.clone())),
+            None => writeln!(f, "cron = \"\""),
+        }?;
+        writeln!(
+            f,
+            "# iCalendar file counted down to in calendar mode (Optional)"
+        )?;
+        writeln!(
+            f,
+            "#  Re-read when changed. Times without a timezone are read in the timezone above."
+        )?;
+        writeln!(f, "#  calendar = \"\" (default)")?;
+        writeln!(f, "#  Example:")?;
+        writeln!(f, "#      calendar = \"/home/user/calendar.ics\"")?;
+        match &self.calendar {
+            Some(calendar) => writeln!(f, "calendar = {}", toml::Value::String(calendar.clone())),
+            None => writeln!(f, "calendar = \"\""),
+        }?;
+        writeln!(f, "# Keep counting down past zero")?;
+        writeln!(
+            f,
+            "#  true            : Render the time past zero as overtime"
+        )?;
+        writeln!(f, "#  false (default) : Stop at zero")?;
+        writeln!(
+            f,
+            "overtime = {}",
+            if self.overtime { "true" } else { "false" }
+        )?;
+        writeln!(f, "# Prefix of overtime")?;
+        writeln!(
+            f,
+            "#  Placed at \"%O\" for custom formats, otherwise in front of the time."
+        )?;
+        writeln!(f, "#  overtime_prefix = \"-\" (default)")?;
+        writeln!(
+            f,
+            "overtime_prefix = {}",
+            toml::Value::String(self.overtime_prefix.clone())
+        )?;
+        writeln!(f, "# Time the machine is suspended in running timers")?;
+        writeln!(f, "#  Exclude (default) : Timers pause while suspended")?;
+        writeln!(
+            f,
+            "#  Include           : Timers keep counting while suspended"
+        )?;
+        writeln!(f, "suspend = \"{:?}\"", self.suspend)?;
         writeln!(f, "# Fonts (Optional)")?;
         #[cfg(target_os = "windows")]
         writeln!(
@@ -36,6 +36,14 @@ pub enum NeedleError {
     FailedToWriteConfig(Box<dyn StdError>),
     #[error("NeedleConfig | Failed to create directory to path. ({0})")]
     FailedToCreateDirectory(Box<dyn StdError>),
+    #[error("NeedleConfig | Invalid timer sequence ({0})")]
+    InvalidSequence(Box<str>),
+    #[error("NeedleConfig | Invalid pomodoro ({0})")]
+    InvalidPomodoro(Box<str>),
+    #[error("NeedleConfig | Invalid alarm ({0})")]
+    InvalidAlarm(Box<str>),
+    #[error("NeedleConfig | Invalid coordinates ({0})")]
+    InvalidCoordinates(Box<str>),
 
     // Surface related errors
     #[error("Surface | Lost")]
@@ -72,6 +80,40 @@ pub enum NeedleError {
     // Clock related errors
     #[error("Clock | Failed to start countup/countdown timer")]
     TimerStartFailure,
+    #[error("Clock | Invalid time format pattern ({0})")]
+    InvalidTimeFormat(Box<str>),
+    #[error("Clock | Invalid timezone ({0})")]
+    InvalidTimeZone(Box<str>),
+    #[error("Clock | Invalid countdown target ({0})")]
+    InvalidTargetTime(Box<str>),
+    #[error("Clock | Invalid duration ({0})")]
+    InvalidDuration(Box<str>),
+    #[error("Clock | Failed to write timer snapshot. ({0})")]
+    FailedToWriteSnapshot(Box<dyn StdError>),
+    #[error("Clock | Failed to read timer snapshot. ({0})")]
+    FailedToReadSnapshot(Box<dyn StdError>),
+    #[error("Clock | Invalid cron expression ({0})")]
+    InvalidCronExpression(Box<str>),
+    #[error("Clock | Invalid splits ({0})")]
+    InvalidSplits(Box<str>),
+    #[error("Clock | Failed to write splits. ({0})")]
+    FailedToWriteSplits(Box<dyn StdError>),
+    #[error("Clock | Failed to read splits. ({0})")]
+    FailedToReadSplits(Box<dyn StdError>),
+    #[error("Clock | Invalid calendar ({0})")]
+    InvalidCalendar(Box<str>),
+    #[error("Clock | Failed to read calendar. ({0})")]
+    FailedToReadCalendar(Box<dyn StdError>),
+    #[error("Clock | Invalid timer name ({0})")]
+    InvalidTimerName(Box<str>),
+    #[error("Clock | Timer not found ({0})")]
+    TimerNotFound(Box<str>),
+    #[error("Clock | Failed to write session history. ({0})")]
+    FailedToWriteHistory(Box<dyn StdError>),
+    #[error("Clock | Failed to read session history. ({0})")]
+    FailedToReadHistory(Box<dyn StdError>),
+    #[error("Clock | Failed to export session history. ({0})")]
+    FailedToExportHistory(Box<dyn StdError>),
 
     // Filesystem related errors
     #[error("Filesystem | Failed to read file")]
@@ -15,6 +15,8 @@ pub struct TextRenderer {
     renderer: glyphon::TextRenderer,
     buffer: Buffer,
     config: Text,
+    /// Additional texts drawn with their own `Text` config, e.g. both sides of a chess clock
+    sides: Vec<(Buffer, Text)>,
     size: PhysicalSize<u32>,
 }
 
@@ -71,6 +73,7 @@ impl TextRenderer {
             renderer,
             buffer,
             config: *config,
+            sides: vec![],
             size: *size,
         })
     }
@@ -91,16 +94,19 @@ impl TextRenderer {
     }
 
     pub fn text_size(&self) -> [f32; 2] {
-        let (width, total_lines) = self
-            .buffer
+        Self::buffer_size(&self.buffer, self.scale())
+    }
+
+    fn buffer_size(buffer: &Buffer, scale: f32) -> [f32; 2] {
+        let (width, total_lines) = buffer
             .layout_runs()
             .fold((0.0, 0usize), |(width, total_lines), run| {
                 (run.line_w.max(width), total_lines + 1)
             });
 
         [
-            width * self.scale(),
-            total_lines as f32 * self.buffer.metrics().line_height * self.scale(),
+            width * scale,
+            total_lines as f32 * buffer.metrics().line_height * scale,
         ]
     }
 
@@ -113,6 +119,40 @@ impl TextRenderer {
         )
     }
 
+    pub fn set_text_with_lines(&mut self, text: &str, lines: &[String]) {
+        if lines.is_empty() {
+            self.set_text(text)
+        } else {
+            self.set_text(&format!("{}\n{}", text, lines.join("\n")))
+        }
+    }
+
+    /// Replaces the additional texts drawn next to the main text.
+    /// Passing an empty slice removes them.
+    pub fn set_side_texts(&mut self, texts: &[(String, Text)]) {
+        let metrics = self.buffer.metrics();
+        let (width, height) = self.buffer.size();
+
+        self.sides.truncate(texts.len());
+
+        while self.sides.len() < texts.len() {
+            let buffer = Buffer::new(&mut self.system, metrics);
+
+            self.sides.push((buffer, self.config));
+        }
+
+        for ((buffer, config), (text, text_config)) in self.sides.iter_mut().zip(texts) {
+            buffer.set_size(&mut self.system, width, height);
+            buffer.set_text(
+                &mut self.system,
+                text,
+                &glyphon::Attrs::new().family(glyphon::Family::Monospace),
+                glyphon::Shaping::Advanced,
+            );
+            *config = *text_config;
+        }
+    }
+
     pub fn set_font(&mut self, font: &str) -> NeedleErr<()> {
         if self.fonts.available_fonts().is_empty() {
             self.fonts.query_fonts(Some(FontTypes::Monospace))?;
@@ -149,32 +189,40 @@ impl super::Renderer for TextRenderer {
     }
 
     fn prepare(&mut self, margin: f32, device: &Device, queue: &Queue) -> NeedleErr<()> {
-        let (left, top) = self.config.position(&self.size, &self.text_size(), margin);
+        let size = self.size;
+        let text_areas = std::iter::once((&self.buffer, &self.config))
+            .chain(self.sides.iter().map(|(buffer, config)| (buffer, config)))
+            .map(|(buffer, config)| {
+                let (left, top) =
+                    config.position(&size, &Self::buffer_size(buffer, config.scale), margin);
+
+                glyphon::TextArea {
+                    buffer,
+                    left,
+                    top,
+                    scale: config.scale,
+                    bounds: glyphon::TextBounds {
+                        left: 0,
+                        top: 0,
+                        right: size.width as i32,
+                        bottom: size.height as i32,
+                    },
+                    default_color: glyphon::Color::rgba(
+                        config.color[0],
+                        config.color[1],
+                        config.color[2],
+                        config.color[3],
+                    ),
+                    custom_glyphs: &[],
+                }
+            });
         let result = self.renderer.prepare(
             device,
             queue,
             &mut self.system,
             &mut self.atlas,
             &self.viewport,
-            [glyphon::TextArea {
-                buffer: &self.buffer,
-                left,
-                top,
-                scale: self.config.scale,
-                bounds: glyphon::TextBounds {
-                    left: 0,
-                    top: 0,
-                    right: self.size.width as i32,
-                    bottom: self.size.height as i32,
-                },
-                default_color: glyphon::Color::rgba(
-                    self.config.color[0],
-                    self.config.color[1],
-                    self.config.color[2],
-                    self.config.color[3],
-                ),
-                custom_glyphs: &[],
-            }],
+            text_areas,
             &mut self.swash_cache,
         );
 
@@ -0,0 +1,570 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+#[cfg(test)]
+use super::utc;
+use super::Zone;
+use crate::{NeedleErr, NeedleError};
+use chrono::{
+    DateTime, Datelike, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+/// When an alarm repeats.
+///
+/// ```toml
+/// repeat = "Daily"
+/// repeat = { Once = "2026-12-01" }
+/// repeat = { Weekdays = ["Mon", "Wed", "Fri"] }
+/// repeat = { Dates = ["2026-12-24", "2026-12-31"] }
+/// repeat = { Every = { minutes = 30, until = "17:00" } }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recurrence {
+    Once(NaiveDate),
+    Daily,
+    Weekdays(Vec<Weekday>),
+    Dates(Vec<NaiveDate>),
+    /// Every `minutes` from the alarm time until `until`, or the end of the day
+    Every {
+        minutes: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        until: Option<NaiveTime>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alarm {
+    #[serde(default)]
+    pub label: String,
+    /// Wall-clock time in the timezone of the clock
+    pub time: NaiveTime,
+    pub repeat: Recurrence,
+    #[serde(default = "Alarm::default_enabled")]
+    pub enabled: bool,
+}
+
+/// Alarm state changes reported by `AlarmScheduler::poll`, carrying the
+/// index of the alarm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmEvent {
+    Fired(usize),
+    Snoozed(usize),
+    Dismissed(usize),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AlarmState {
+    next: Option<DateTime<Utc>>,
+    snoozed: Option<DateTime<Utc>>,
+    ringing: bool,
+}
+
+/// Fires `Alarm`s in a timezone. Driven by calling `AlarmScheduler::poll`
+/// every frame.
+#[derive(Debug, Clone, Default)]
+pub struct AlarmScheduler {
+    alarms: Vec<Alarm>,
+    zone: Zone,
+    states: Vec<AlarmState>,
+    events: Vec<AlarmEvent>,
+}
+
+impl Recurrence {
+    /// Days to look ahead for repeating alarms, covering the day before and
+    /// a whole week after the current day
+    const LOOKAHEAD_DAYS: usize = 9;
+
+    /// Dates on or after `from` the alarm may fire on, in order
+    fn dates(&self, from: NaiveDate) -> Vec<NaiveDate> {
+        match self {
+            Self::Once(date) => [*date].into_iter().filter(|date| *date >= from).collect(),
+            Self::Dates(dates) => {
+                let mut dates = dates
+                    .iter()
+                    .copied()
+                    .filter(|date| *date >= from)
+                    .collect::<Vec<_>>();
+
+                dates.sort();
+                dates
+            }
+            Self::Daily | Self::Every { .. } => {
+                from.iter_days().take(Self::LOOKAHEAD_DAYS).collect()
+            }
+            Self::Weekdays(weekdays) => from
+                .iter_days()
+                .take(Self::LOOKAHEAD_DAYS)
+                .filter(|date| weekdays.contains(&date.weekday()))
+                .collect(),
+        }
+    }
+
+    /// Times of day starting at `time` the alarm fires at
+    fn times(&self, time: NaiveTime) -> Vec<NaiveTime> {
+        match self {
+            Self::Every { minutes, until } if *minutes > 0 => {
+                let step = TimeDelta::minutes(*minutes as i64);
+                let end = until.unwrap_or(NaiveTime::MIN - TimeDelta::nanoseconds(1));
+                let mut times = vec![];
+                let mut next = Some(time);
+
+                while let Some(current) = next.filter(|current| *current <= end) {
+                    let (added, wrapped) = current.overflowing_add_signed(step);
+
+                    times.push(current);
+                    // Stop at midnight
+                    next = (wrapped == 0).then_some(added);
+                }
+
+                times
+            }
+            _ => vec![time],
+        }
+    }
+}
+
+impl Alarm {
+    /// Length of the longest DST gap to skip over
+    const MAX_GAP_MINUTES: i64 = 3 * 60;
+
+    fn default_enabled() -> bool {
+        true
+    }
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        let invalid = |reason: &str| {
+            Err(NeedleError::InvalidAlarm(
+                format!("\"{}\": {}", self.label, reason).into(),
+            ))
+        };
+
+        match self.repeat {
+            Recurrence::Weekdays(ref weekdays) if weekdays.is_empty() => {
+                invalid("weekdays are empty")
+            }
+            Recurrence::Dates(ref dates) if dates.is_empty() => invalid("dates are empty"),
+            Recurrence::Every { minutes: 0, .. } => invalid("minutes must be 1 or more"),
+            Recurrence::Every {
+                until: Some(until), ..
+            } if until < self.time => invalid("until is earlier than time"),
+            _ => Ok(()),
+        }
+    }
+
+    /// First time the alarm fires strictly after `after`.
+    ///
+    /// A time skipped by a DST transition fires as the clock jumps over it,
+    /// a time repeated by one fires on its first occurrence only.
+    pub fn next_fire(&self, zone: &Zone, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !self.enabled {
+            return None;
+        }
+
+        // Start a day early as the date in `zone` may lag behind
+        let from = zone.to_local(after).date().pred_opt()?;
+        let times = self.repeat.times(self.time);
+
+        self.repeat.dates(from).into_iter().find_map(|date| {
+            times
+                .iter()
+                .filter_map(|time| Self::resolve(zone, &date.and_time(*time)))
+                .find(|fire| fire > after)
+        })
+    }
+
+    fn resolve(zone: &Zone, naive: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        match zone.from_local(naive) {
+            LocalResult::Single(time) => Some(time),
+            LocalResult::Ambiguous(earliest, _) => Some(earliest),
+            LocalResult::None => (1..=Self::MAX_GAP_MINUTES).find_map(|minutes| {
+                zone.from_local(&(*naive + TimeDelta::minutes(minutes)))
+                    .earliest()
+            }),
+        }
+    }
+}
+
+impl AlarmScheduler {
+    pub const DEFAULT_SNOOZE: Duration = Duration::from_secs(5 * 60);
+
+    pub fn new(alarms: &[Alarm], zone: Zone, now: &DateTime<Utc>) -> Self {
+        let mut scheduler = Self {
+            alarms: alarms.to_vec(),
+            zone,
+            states: vec![AlarmState::default(); alarms.len()],
+            events: vec![],
+        };
+
+        scheduler.reschedule(now);
+
+        scheduler
+    }
+
+    #[inline]
+    pub fn alarms(&self) -> &[Alarm] {
+        &self.alarms
+    }
+
+    /// Recomputes the next occurrences in `zone`. Snoozes are kept.
+    pub fn set_zone(&mut self, zone: Zone, now: &DateTime<Utc>) {
+        self.zone = zone;
+        self.reschedule(now);
+    }
+
+    /// Next time the alarm at `index` fires, including snoozes
+    pub fn next(&self, index: usize) -> Option<DateTime<Utc>> {
+        self.states.get(index).and_then(|state| match state {
+            AlarmState {
+                snoozed: Some(snoozed),
+                next: Some(next),
+                ..
+            } => Some(*snoozed.min(next)),
+            AlarmState { snoozed, next, .. } => snoozed.or(*next),
+        })
+    }
+
+    /// Alarm firing first with its fire time
+    pub fn next_alarm(&self) -> Option<(usize, DateTime<Utc>)> {
+        (0..self.alarms.len())
+            .filter_map(|index| self.next(index).map(|next| (index, next)))
+            .min_by_key(|(_, next)| *next)
+    }
+
+    pub fn ringing(&self) -> Vec<usize> {
+        self.states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| state.ringing)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Fires alarms due at `now` and takes the events raised since the last call
+    pub fn poll(&mut self, now: &DateTime<Utc>) -> Vec<AlarmEvent> {
+        for (index, alarm) in self.alarms.iter().enumerate() {
+            let state = &mut self.states[index];
+            let snooze_over = state.snoozed.is_some_and(|snoozed| snoozed <= *now);
+            let due = state.next.is_some_and(|next| next <= *now);
+
+            if due {
+                // Occurrences missed while not polled are skipped
+                state.next = alarm.next_fire(&self.zone, now);
+            }
+
+            if snooze_over || due {
+                state.snoozed = None;
+
+                if !state.ringing {
+                    state.ringing = true;
+                    self.events.push(AlarmEvent::Fired(index));
+                }
+            }
+        }
+
+        std::mem::take(&mut self.events)
+    }
+
+    /// Silences a ringing alarm and fires it again after `duration`
+    pub fn snooze(&mut self, index: usize, duration: Duration, now: &DateTime<Utc>) -> bool {
+        match self.states.get_mut(index) {
+            Some(state) if state.ringing => {
+                state.ringing = false;
+                state.snoozed =
+                    Some(*now + TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX));
+                self.events.push(AlarmEvent::Snoozed(index));
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Silences a ringing or snoozed alarm until its next occurrence
+    pub fn dismiss(&mut self, index: usize) -> bool {
+        match self.states.get_mut(index) {
+            Some(state) if state.ringing || state.snoozed.is_some() => {
+                state.ringing = false;
+                state.snoozed = None;
+                self.events.push(AlarmEvent::Dismissed(index));
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn reschedule(&mut self, now: &DateTime<Utc>) {
+        for (alarm, state) in self.alarms.iter().zip(self.states.iter_mut()) {
+            state.next = alarm.next_fire(&self.zone, now);
+        }
+    }
+}
+
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let list = |items: Vec<String>| {
+            items
+                .iter()
+                .map(|item| format!("\"{}\"", item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match self {
+            Self::Once(date) => write!(f, "{{ Once = \"{}\" }}", date),
+            Self::Daily => write!(f, "\"Daily\""),
+            Self::Weekdays(weekdays) => write!(
+                f,
+                "{{ Weekdays = [{}] }}",
+                list(weekdays.iter().map(|weekday| weekday.to_string()).collect())
+            ),
+            Self::Dates(dates) => write!(
+                f,
+                "{{ Dates = [{}] }}",
+                list(dates.iter().map(|date| date.to_string()).collect())
+            ),
+            Self::Every {
+                minutes,
+                until: Some(until),
+            } => write!(
+                f,
+                "{{ Every = {{ minutes = {}, until = \"{}\" }} }}",
+                minutes, until
+            ),
+            Self::Every {
+                minutes,
+                until: None,
+            } => write!(f, "{{ Every = {{ minutes = {} }} }}", minutes),
+        }
+    }
+}
+
+impl Display for Alarm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "label = {}", toml::Value::String(self.label.clone()))?;
+        writeln!(f, "time = \"{}\"", self.time)?;
+        writeln!(f, "repeat = {}", self.repeat)?;
+        write!(
+            f,
+            "enabled = {}",
+            if self.enabled { "true" } else { "false" }
+        )
+    }
+}
+
+#[cfg(test)]
+fn alarm(time: &str, repeat: Recurrence) -> Alarm {
+    Alarm {
+        label: "Test".to_string(),
+        time: time.parse().unwrap(),
+        repeat,
+        enabled: true,
+    }
+}
+
+#[test]
+fn test_alarm_0001() {
+    let zone = Zone::parse("UTC").unwrap();
+    // Wednesday
+    let now = utc("2025-01-01T12:00:00Z");
+    let next = |alarm: Alarm| alarm.next_fire(&zone, &now);
+
+    assert_eq!(
+        Some(utc("2025-01-02T07:30:00Z")),
+        next(alarm("07:30", Recurrence::Daily))
+    );
+    assert_eq!(
+        Some(utc("2025-01-01T12:00:01Z")),
+        next(alarm("12:00:01", Recurrence::Daily))
+    );
+    assert_eq!(
+        Some(utc("2025-01-06T07:30:00Z")),
+        next(alarm(
+            "07:30",
+            Recurrence::Weekdays(vec![Weekday::Mon, Weekday::Tue])
+        ))
+    );
+    assert_eq!(
+        Some(utc("2025-01-08T07:30:00Z")),
+        next(alarm("07:30", Recurrence::Weekdays(vec![Weekday::Wed])))
+    );
+    assert_eq!(
+        None,
+        next(alarm(
+            "07:30",
+            Recurrence::Once("2025-01-01".parse().unwrap())
+        ))
+    );
+    assert_eq!(
+        Some(utc("2026-12-24T18:00:00Z")),
+        next(alarm(
+            "18:00",
+            Recurrence::Dates(vec![
+                "2026-12-31".parse().unwrap(),
+                "2026-12-24".parse().unwrap(),
+                "2024-12-24".parse().unwrap(),
+            ])
+        ))
+    );
+
+    let every = alarm(
+        "09:00",
+        Recurrence::Every {
+            minutes: 25,
+            until: Some("12:30".parse().unwrap()),
+        },
+    );
+
+    // 09:00, 09:25, ..., 11:55, 12:20
+    assert_eq!(
+        Some(utc("2025-01-01T12:20:00Z")),
+        every.next_fire(&zone, &now)
+    );
+    assert_eq!(
+        Some(utc("2025-01-02T09:00:00Z")),
+        every.next_fire(&zone, &utc("2025-01-01T12:20:00Z"))
+    );
+    assert_eq!(
+        Some(utc("2025-01-01T23:59:00Z")),
+        alarm(
+            "23:59",
+            Recurrence::Every {
+                minutes: 1,
+                until: None,
+            }
+        )
+        .next_fire(&zone, &now)
+    );
+
+    let disabled = Alarm {
+        enabled: false,
+        ..alarm("07:30", Recurrence::Daily)
+    };
+
+    assert_eq!(None, next(disabled))
+}
+
+#[test]
+fn test_alarm_0002() {
+    let zone = Zone::parse("America/New_York").unwrap();
+    let daily = |time| alarm(time, Recurrence::Daily);
+
+    // 2025-03-09 02:00 EST jumps to 03:00 EDT: 02:30 fires as the clock jumps
+    assert_eq!(
+        Some(utc("2025-03-09T07:00:00Z")),
+        daily("02:30").next_fire(&zone, &utc("2025-03-09T05:00:00Z"))
+    );
+    assert_eq!(
+        Some(utc("2025-03-10T06:30:00Z")),
+        daily("02:30").next_fire(&zone, &utc("2025-03-09T07:00:00Z"))
+    );
+    // 2025-11-02 02:00 EDT falls back to 01:00 EST: 01:30 fires once
+    assert_eq!(
+        Some(utc("2025-11-02T05:30:00Z")),
+        daily("01:30").next_fire(&zone, &utc("2025-11-02T04:00:00Z"))
+    );
+    assert_eq!(
+        Some(utc("2025-11-03T06:30:00Z")),
+        daily("01:30").next_fire(&zone, &utc("2025-11-02T05:30:00Z"))
+    );
+
+    let every = alarm(
+        "01:45",
+        Recurrence::Every {
+            minutes: 15,
+            until: Some("03:15".parse().unwrap()),
+        },
+    );
+
+    // 02:00 to 02:45 collapse into 03:00 EDT, fired once
+    assert_eq!(
+        Some(utc("2025-03-09T07:00:00Z")),
+        every.next_fire(&zone, &utc("2025-03-09T06:45:00Z"))
+    );
+    assert_eq!(
+        Some(utc("2025-03-09T07:15:00Z")),
+        every.next_fire(&zone, &utc("2025-03-09T07:00:00Z"))
+    )
+}
+
+#[test]
+fn test_alarm_0003() {
+    let zone = Zone::parse("UTC").unwrap();
+    let alarms = [
+        alarm("12:01", Recurrence::Daily),
+        alarm("12:05", Recurrence::Once("2025-01-01".parse().unwrap())),
+    ];
+    let mut scheduler = AlarmScheduler::new(&alarms, zone, &utc("2025-01-01T12:00:00Z"));
+
+    assert_eq!(
+        Some((0, utc("2025-01-01T12:01:00Z"))),
+        scheduler.next_alarm()
+    );
+    assert!(scheduler.poll(&utc("2025-01-01T12:00:59Z")).is_empty());
+    assert_eq!(
+        vec![AlarmEvent::Fired(0)],
+        scheduler.poll(&utc("2025-01-01T12:01:00Z"))
+    );
+    assert_eq!(vec![0], scheduler.ringing());
+    assert!(scheduler.poll(&utc("2025-01-01T12:01:30Z")).is_empty());
+
+    // Snoozed until 12:06, after the one-off alarm
+    assert!(scheduler.snooze(0, Duration::from_secs(300), &utc("2025-01-01T12:01:00Z")));
+    assert_eq!(
+        Some((1, utc("2025-01-01T12:05:00Z"))),
+        scheduler.next_alarm()
+    );
+    assert_eq!(
+        vec![AlarmEvent::Snoozed(0), AlarmEvent::Fired(1)],
+        scheduler.poll(&utc("2025-01-01T12:05:00Z"))
+    );
+    assert!(scheduler.dismiss(1));
+    assert!(!scheduler.dismiss(1));
+    assert_eq!(None, scheduler.next(1));
+    assert_eq!(
+        vec![AlarmEvent::Dismissed(1), AlarmEvent::Fired(0)],
+        scheduler.poll(&utc("2025-01-01T12:06:00Z"))
+    );
+    assert!(scheduler.dismiss(0));
+    assert_eq!(
+        Some((0, utc("2025-01-02T12:01:00Z"))),
+        scheduler.next_alarm()
+    );
+
+    // Zone change reschedules
+    scheduler.set_zone(Zone::parse("+09:00").unwrap(), &utc("2025-01-01T12:06:00Z"));
+    assert_eq!(
+        Some((0, utc("2025-01-02T03:01:00Z"))),
+        scheduler.next_alarm()
+    )
+}
+
+#[test]
+fn test_alarm_0004() {
+    let invalid = [
+        alarm("07:30", Recurrence::Weekdays(vec![])),
+        alarm("07:30", Recurrence::Dates(vec![])),
+        alarm(
+            "07:30",
+            Recurrence::Every {
+                minutes: 0,
+                until: None,
+            },
+        ),
+        alarm(
+            "07:30",
+            Recurrence::Every {
+                minutes: 5,
+                until: Some("07:00".parse().unwrap()),
+            },
+        ),
+    ];
+
+    assert!(invalid.iter().all(|alarm| alarm.validate().is_err()));
+    assert!(alarm("07:30", Recurrence::Daily).validate().is_ok())
+}
@@ -0,0 +1,782 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+#[cfg(test)]
+use super::utc;
+use super::Zone;
+use crate::{NeedleErr, NeedleError};
+use chrono::{
+    DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc, Weekday,
+};
+use ical::{parser::ical::IcalParser, property::Property};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Events of an iCalendar (`.ics`) file, counted down to by `OpMode::Calendar`.
+///
+/// Floating times are read in the timezone given to `Calendar::parse`, or in
+/// `X-WR-TIMEZONE` of the calendar when set. `TZID` must be an IANA timezone
+/// name, as `VTIMEZONE` definitions aren't read.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Calendar {
+    events: Vec<CalendarEvent>,
+}
+
+/// A `VEVENT` with its recurrence
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    /// Wall-clock time of `DTSTART` in `zone`
+    start: NaiveDateTime,
+    zone: Zone,
+    rule: Option<RecurrenceRule>,
+    /// Instances removed by `EXDATE` or replaced by `RECURRENCE-ID`
+    exceptions: Vec<DateTime<Utc>>,
+}
+
+/// Next event to start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpcomingEvent {
+    pub summary: String,
+    pub start: DateTime<Utc>,
+}
+
+/// `RRULE` of RFC 5545, limited to `DAILY`, `WEEKLY`, `MONTHLY` and `YEARLY`
+/// frequencies with `BYDAY`, `BYMONTHDAY` and `BYMONTH`.
+#[derive(Debug, Clone, PartialEq)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<DateTime<Utc>>,
+    /// Weekday with an optional ordinal, e.g. `-1FR` for the last Friday
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// `Calendar` read from a file, re-read when the file changes on disk.
+#[derive(Debug)]
+pub struct CalendarSource {
+    path: PathBuf,
+    zone: Zone,
+    modified: Option<SystemTime>,
+    checked: Option<Instant>,
+    calendar: Calendar,
+}
+
+impl Calendar {
+    const UTC: Zone = Zone::Named(chrono_tz::UTC);
+
+    pub fn from(path: &Path, zone: Zone) -> NeedleErr<Self> {
+        if !path.exists() {
+            let path = path.to_string_lossy();
+
+            return Err(NeedleError::ConfigNonExistant(path.into()));
+        }
+
+        match fs::read_to_string(path) {
+            Ok(ics) => Self::parse(&ics, zone),
+            Err(err) => Err(NeedleError::FailedToReadCalendar(err.into())),
+        }
+    }
+
+    pub fn parse(ics: &str, zone: Zone) -> NeedleErr<Self> {
+        let mut events = vec![];
+        let mut overrides = vec![];
+
+        for calendar in IcalParser::new(ics.as_bytes()) {
+            let calendar =
+                calendar.map_err(|err| NeedleError::InvalidCalendar(err.to_string().into()))?;
+            let zone = Self::property(&calendar.properties, "X-WR-TIMEZONE")
+                .and_then(|property| property.value.as_deref())
+                .and_then(|name| Zone::parse(name).ok())
+                .unwrap_or(zone);
+
+            for event in calendar.events {
+                let properties = &event.properties;
+                let recurrence_id = match Self::property(properties, "RECURRENCE-ID") {
+                    Some(property) => Some(Self::date_time(property, zone)?),
+                    None => None,
+                };
+                let cancelled = Self::property(properties, "STATUS")
+                    .and_then(|property| property.value.as_deref())
+                    .is_some_and(|status| status.eq_ignore_ascii_case("CANCELLED"));
+                let event = CalendarEvent::parse(properties, zone)?;
+
+                if let Some(recurrence_id) = recurrence_id {
+                    overrides.push((event.uid.clone(), recurrence_id));
+                }
+                if !cancelled {
+                    events.push(event);
+                }
+            }
+        }
+
+        // Moved or cancelled instances of a recurring event
+        for (uid, recurrence_id) in overrides {
+            events
+                .iter_mut()
+                .filter(|event| event.uid == uid && event.rule.is_some())
+                .for_each(|event| event.exceptions.push(recurrence_id));
+        }
+
+        Ok(Self { events })
+    }
+
+    #[inline]
+    pub fn events(&self) -> &[CalendarEvent] {
+        &self.events
+    }
+
+    /// Event starting first after `after`
+    pub fn next_event(&self, after: &DateTime<Utc>) -> Option<UpcomingEvent> {
+        self.events
+            .iter()
+            .filter_map(|event| {
+                event.next_after(after).map(|start| UpcomingEvent {
+                    summary: event.summary.clone(),
+                    start,
+                })
+            })
+            .min_by_key(|event| event.start)
+    }
+
+    fn property<'a>(properties: &'a [Property], name: &str) -> Option<&'a Property> {
+        properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case(name))
+    }
+
+    fn param<'a>(property: &'a Property, name: &str) -> Option<&'a str> {
+        property
+            .params
+            .as_ref()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))?
+            .1
+            .first()
+            .map(|value| value.as_str())
+    }
+
+    /// Zone of a `DATE` or `DATE-TIME` property
+    fn zone_of(property: &Property, zone: Zone) -> Zone {
+        Self::param(property, "TZID")
+            .map(|tzid| tzid.trim_matches(['"', '/']))
+            .and_then(|tzid| Zone::parse(tzid).ok())
+            .unwrap_or(zone)
+    }
+
+    fn date_time(property: &Property, zone: Zone) -> NeedleErr<DateTime<Utc>> {
+        let value = property.value.as_deref().unwrap_or_default();
+        let (naive, zone) = Self::local_date_time(value, Self::zone_of(property, zone))?;
+
+        Ok(CalendarEvent::resolve(&naive, zone))
+    }
+
+    /// Parses `YYYYMMDD`, `YYYYMMDDTHHMMSS` or `YYYYMMDDTHHMMSSZ`
+    fn local_date_time(value: &str, zone: Zone) -> NeedleErr<(NaiveDateTime, Zone)> {
+        let value = value.trim();
+        let invalid = || NeedleError::InvalidCalendar(format!("invalid date \"{}\"", value).into());
+
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+            return Ok((date.and_time(NaiveTime::MIN), zone));
+        }
+
+        let (value, zone) = match value.strip_suffix(['Z', 'z']) {
+            Some(value) => (value, Self::UTC),
+            None => (value, zone),
+        };
+
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .map(|naive| (naive, zone))
+            .map_err(|_| invalid())
+    }
+}
+
+impl CalendarEvent {
+    /// Recurrences are expanded up to this many days, weeks, months or years
+    const MAX_PERIODS: u32 = 50_000;
+
+    fn parse(properties: &[Property], zone: Zone) -> NeedleErr<Self> {
+        let text = |name| {
+            Calendar::property(properties, name)
+                .and_then(|property| property.value.clone())
+                .unwrap_or_default()
+        };
+        let start = Calendar::property(properties, "DTSTART")
+            .ok_or_else(|| NeedleError::InvalidCalendar("event without DTSTART".into()))?;
+        let (start, zone) = Calendar::local_date_time(
+            start.value.as_deref().unwrap_or_default(),
+            Calendar::zone_of(start, zone),
+        )?;
+        let rule = match Calendar::property(properties, "RRULE") {
+            Some(property) => Some(RecurrenceRule::parse(
+                property.value.as_deref().unwrap_or_default(),
+                zone,
+            )?),
+            None => None,
+        };
+        let mut exceptions = vec![];
+
+        for property in properties
+            .iter()
+            .filter(|property| property.name.eq_ignore_ascii_case("EXDATE"))
+        {
+            let exdate_zone = Calendar::zone_of(property, zone);
+
+            for value in property.value.as_deref().unwrap_or_default().split(',') {
+                let (naive, exdate_zone) = Calendar::local_date_time(value, exdate_zone)?;
+
+                exceptions.push(Self::resolve(&naive, exdate_zone));
+            }
+        }
+
+        Ok(Self {
+            uid: text("UID"),
+            summary: text("SUMMARY"),
+            start,
+            zone,
+            rule,
+            exceptions,
+        })
+    }
+
+    /// First instance starting strictly after `after`
+    pub fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let Some(ref rule) = self.rule else {
+            let start = Self::resolve(&self.start, self.zone);
+
+            return (start > *after && !self.exceptions.contains(&start)).then_some(start);
+        };
+        let mut count = 0;
+
+        for period in 0..Self::MAX_PERIODS {
+            let mut dates = rule.dates(self.start.date(), period)?;
+
+            dates.sort();
+            dates.dedup();
+
+            for date in dates {
+                let start = date.and_time(self.start.time());
+
+                if start < self.start {
+                    continue;
+                }
+
+                let start = Self::resolve(&start, self.zone);
+
+                count += 1;
+                if rule.until.is_some_and(|until| start > until)
+                    || rule.count.is_some_and(|limit| count > limit)
+                {
+                    return None;
+                }
+                if start > *after && !self.exceptions.contains(&start) {
+                    return Some(start);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Times skipped by a DST transition are moved past the gap
+    fn resolve(naive: &NaiveDateTime, zone: Zone) -> DateTime<Utc> {
+        zone.from_local(naive)
+            .earliest()
+            .or_else(|| zone.from_local(&(*naive + TimeDelta::hours(1))).earliest())
+            .unwrap_or_else(|| naive.and_utc())
+    }
+}
+
+impl RecurrenceRule {
+    fn parse(rule: &str, zone: Zone) -> NeedleErr<Self> {
+        let invalid = |reason: &str| {
+            NeedleError::InvalidCalendar(format!("RRULE \"{}\": {}", rule, reason).into())
+        };
+        let number = |value: &str| value.parse::<i32>().map_err(|_| invalid("invalid number"));
+        let mut frequency = None;
+        let mut parsed = Self {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: vec![],
+            by_month_day: vec![],
+            by_month: vec![],
+        };
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid("expected KEY=VALUE"))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(invalid("unsupported FREQ")),
+                    })
+                }
+                "INTERVAL" => {
+                    parsed.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|interval| *interval > 0)
+                        .ok_or_else(|| invalid("invalid INTERVAL"))?
+                }
+                "COUNT" => {
+                    parsed.count = Some(value.parse().map_err(|_| invalid("invalid COUNT"))?)
+                }
+                "UNTIL" => {
+                    let (naive, zone) = Calendar::local_date_time(value, zone)?;
+
+                    parsed.until = Some(CalendarEvent::resolve(&naive, zone))
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        let split = day.len().saturating_sub(2);
+                        let (nth, weekday) = day
+                            .get(..split)
+                            .zip(day.get(split..))
+                            .ok_or_else(|| invalid("invalid BYDAY"))?;
+                        let weekday =
+                            Self::weekday(weekday).ok_or_else(|| invalid("invalid BYDAY"))?;
+                        let nth = match nth {
+                            "" => None,
+                            nth => Some(number(nth)?),
+                        };
+
+                        parsed.by_day.push((nth, weekday))
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        parsed.by_month_day.push(number(day)?)
+                    }
+                }
+                "BYMONTH" => {
+                    for month in value.split(',') {
+                        match number(month)? {
+                            month @ 1..=12 => parsed.by_month.push(month as u32),
+                            _ => return Err(invalid("invalid BYMONTH")),
+                        }
+                    }
+                }
+                // Weeks always start on Monday
+                "WKST" => (),
+                _ => return Err(invalid(&format!("unsupported {}", key))),
+            }
+        }
+
+        parsed.frequency = frequency.ok_or_else(|| invalid("FREQ is missing"))?;
+
+        Ok(parsed)
+    }
+
+    fn weekday(day: &str) -> Option<Weekday> {
+        match day.to_ascii_uppercase().as_str() {
+            "MO" => Some(Weekday::Mon),
+            "TU" => Some(Weekday::Tue),
+            "WE" => Some(Weekday::Wed),
+            "TH" => Some(Weekday::Thu),
+            "FR" => Some(Weekday::Fri),
+            "SA" => Some(Weekday::Sat),
+            "SU" => Some(Weekday::Sun),
+            _ => None,
+        }
+    }
+
+    /// Candidate dates of the `period`th period counted from `start`.
+    /// `None` once the period is out of the representable range.
+    fn dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+        let in_month =
+            |date: &NaiveDate| self.by_month.is_empty() || self.by_month.contains(&date.month());
+
+        let dates = match self.frequency {
+            Frequency::Daily => {
+                let date = start.checked_add_days(Days::new(step as u64))?;
+                let days = Self::days_in_month(&date);
+                let matches = in_month(&date)
+                    && (self.by_month_day.is_empty()
+                        || self
+                            .by_month_day
+                            .iter()
+                            .any(|day| Self::month_day(*day, days) == Some(date.day())))
+                    && (self.by_day.is_empty()
+                        || self.by_day.iter().any(|(_, day)| *day == date.weekday()));
+
+                if matches {
+                    vec![date]
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Weekly => {
+                let monday = start
+                    .checked_sub_days(Days::new(start.weekday().num_days_from_monday() as u64))?
+                    .checked_add_days(Days::new(step as u64 * 7))?;
+                let weekdays = if self.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, day)| *day).collect()
+                };
+
+                weekdays
+                    .into_iter()
+                    .filter_map(|day| {
+                        monday.checked_add_days(Days::new(day.num_days_from_monday() as u64))
+                    })
+                    .filter(in_month)
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let month = start.with_day(1)?.checked_add_months(Months::new(step))?;
+
+                if in_month(&month) {
+                    self.month_dates(&month, start.day())
+                } else {
+                    vec![]
+                }
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(step as i32)?;
+                let months = if !self.by_month.is_empty() {
+                    self.by_month.clone()
+                } else if !self.by_day.is_empty() || !self.by_month_day.is_empty() {
+                    (1..=12).collect()
+                } else {
+                    vec![start.month()]
+                };
+
+                months
+                    .into_iter()
+                    .filter_map(|month| NaiveDate::from_ymd_opt(year, month, 1))
+                    .flat_map(|month| self.month_dates(&month, start.day()))
+                    .collect()
+            }
+        };
+
+        Some(dates)
+    }
+
+    /// Dates within the month of `first` matching `BYMONTHDAY` and `BYDAY`,
+    /// or `day` when neither is given
+    fn month_dates(&self, first: &NaiveDate, day: u32) -> Vec<NaiveDate> {
+        let days = Self::days_in_month(first);
+        let mut dates: Vec<NaiveDate> = if !self.by_month_day.is_empty() {
+            self.by_month_day
+                .iter()
+                .filter_map(|day| Self::month_day(*day, days))
+                .filter_map(|day| first.with_day(day))
+                .collect()
+        } else if self.by_day.is_empty() {
+            // Months without the day, e.g. the 31st, are skipped
+            first.with_day(day).into_iter().collect()
+        } else {
+            (1..=days).filter_map(|day| first.with_day(day)).collect()
+        };
+
+        if !self.by_day.is_empty() {
+            dates.retain(|date| {
+                let first = (date.day() as i32 - 1) / 7 + 1;
+                let last = -((days as i32 - date.day() as i32) / 7 + 1);
+
+                self.by_day.iter().any(|(nth, day)| {
+                    *day == date.weekday() && nth.is_none_or(|nth| nth == first || nth == last)
+                })
+            })
+        }
+
+        dates
+    }
+
+    /// Day of month counted from the end when negative
+    fn month_day(day: i32, days: u32) -> Option<u32> {
+        let day = if day < 0 { days as i32 + day + 1 } else { day };
+
+        (1..=days as i32).contains(&day).then_some(day as u32)
+    }
+
+    fn days_in_month(date: &NaiveDate) -> u32 {
+        let first = date.with_day(1).unwrap_or(*date);
+
+        first
+            .checked_add_months(Months::new(1))
+            .map(|next| next.signed_duration_since(first).num_days() as u32)
+            .unwrap_or(31)
+    }
+}
+
+impl CalendarSource {
+    /// Interval the modification time of the file is checked at
+    pub const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn new(path: &Path, zone: Zone) -> NeedleErr<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            zone,
+            modified: Self::modified(path),
+            checked: None,
+            calendar: Calendar::from(path, zone)?,
+        })
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    /// Re-reads the file when it changed on disk, checking at most once per
+    /// `RELOAD_INTERVAL`. Returns whether the calendar has been replaced.
+    ///
+    /// On error the events read before are kept, and the file isn't read
+    /// again until it changes.
+    pub fn poll(&mut self, now: Instant) -> NeedleErr<bool> {
+        if self
+            .checked
+            .is_some_and(|checked| now.duration_since(checked) < Self::RELOAD_INTERVAL)
+        {
+            return Ok(false);
+        }
+        self.checked = Some(now);
+
+        let modified = Self::modified(&self.path);
+
+        if modified == self.modified {
+            return Ok(false);
+        }
+        self.modified = modified;
+        self.calendar = Calendar::from(&self.path, self.zone)?;
+
+        Ok(true)
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+#[cfg(test)]
+fn ics(events: &[&str]) -> String {
+    let events = events
+        .iter()
+        .map(|event| format!("BEGIN:VEVENT\r\n{}\r\nEND:VEVENT\r\n", event.trim()))
+        .collect::<String>();
+
+    format!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//needle//test//EN\r\n{}END:VCALENDAR\r\n",
+        events
+    )
+}
+
+#[test]
+fn test_calendar_0001() {
+    let calendar = Calendar::parse(
+        &ics(&[
+            "UID:standup\nSUMMARY:Standup\nDTSTART;TZID=Asia/Tokyo:20250102T093000",
+            "UID:release\nSUMMARY:Release\nDTSTART:20250101T150000Z",
+            "UID:holiday\nSUMMARY:Holiday\nDTSTART;VALUE=DATE:20250101",
+            "UID:past\nSUMMARY:Past\nDTSTART:20241231T000000Z",
+        ]),
+        Zone::parse("+02:00").unwrap(),
+    )
+    .unwrap();
+    let next = |after| calendar.next_event(&utc(after));
+
+    assert_eq!(4, calendar.events().len());
+    // All-day events start at midnight of the floating timezone
+    assert_eq!(
+        Some(UpcomingEvent {
+            summary: "Holiday".to_string(),
+            start: utc("2024-12-31T22:00:00Z"),
+        }),
+        next("2024-12-31T12:00:00Z")
+    );
+    assert_eq!(
+        Some(UpcomingEvent {
+            summary: "Release".to_string(),
+            start: utc("2025-01-01T15:00:00Z"),
+        }),
+        next("2025-01-01T12:34:56Z")
+    );
+    assert_eq!(
+        Some(utc("2025-01-02T00:30:00Z")),
+        next("2025-01-01T15:00:00Z").map(|event| event.start)
+    );
+    assert_eq!(None, next("2025-01-02T00:30:00Z"))
+}
+
+#[test]
+fn test_calendar_0002() {
+    let calendar = Calendar::parse(
+        &ics(&[
+            // Mondays and Wednesdays of every other week, 5 times
+            "UID:gym\nSUMMARY:Gym\nDTSTART:20250106T180000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=5",
+        ]),
+        Zone::Local,
+    )
+    .unwrap();
+    let event = &calendar.events()[0];
+    let mut after = utc("2025-01-01T00:00:00Z");
+    let mut starts = vec![];
+
+    while let Some(start) = event.next_after(&after) {
+        starts.push(start);
+        after = start;
+    }
+
+    assert_eq!(
+        vec![
+            utc("2025-01-06T18:00:00Z"),
+            utc("2025-01-08T18:00:00Z"),
+            utc("2025-01-20T18:00:00Z"),
+            utc("2025-01-22T18:00:00Z"),
+            utc("2025-02-03T18:00:00Z"),
+        ],
+        starts
+    )
+}
+
+#[test]
+fn test_calendar_0003() {
+    let zone = Zone::parse("Europe/London").unwrap();
+    let calendar = Calendar::parse(
+        &ics(&[
+            // Last Friday of the month, in local time across DST
+            "UID:review\nSUMMARY:Review\nDTSTART;TZID=Europe/London:20250131T160000\nRRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20250601T000000Z",
+            // Yearly on the 29th of February only
+            "UID:leap\nSUMMARY:Leap\nDTSTART;VALUE=DATE:20240229\nRRULE:FREQ=YEARLY",
+        ]),
+        zone,
+    )
+    .unwrap();
+    let review = &calendar.events()[0];
+    let leap = &calendar.events()[1];
+
+    assert_eq!(
+        Some(utc("2025-02-28T16:00:00Z")),
+        review.next_after(&utc("2025-01-31T16:00:00Z"))
+    );
+    // British Summer Time
+    assert_eq!(
+        Some(utc("2025-04-25T15:00:00Z")),
+        review.next_after(&utc("2025-03-28T16:00:00Z"))
+    );
+    assert_eq!(
+        Some(utc("2025-05-30T15:00:00Z")),
+        review.next_after(&utc("2025-04-25T15:00:00Z"))
+    );
+    assert_eq!(None, review.next_after(&utc("2025-05-30T15:00:00Z")));
+    assert_eq!(
+        Some(utc("2028-02-29T00:00:00Z")),
+        leap.next_after(&utc("2024-03-01T00:00:00Z"))
+    )
+}
+
+#[test]
+fn test_calendar_0004() {
+    let calendar = Calendar::parse(
+        &ics(&[
+            "UID:daily\nSUMMARY:Daily\nDTSTART:20250101T090000Z\nRRULE:FREQ=DAILY\nEXDATE:20250102T090000Z,20250103T090000Z",
+            // The 4th is moved to the afternoon, the 5th is cancelled
+            "UID:daily\nSUMMARY:Daily (moved)\nRECURRENCE-ID:20250104T090000Z\nDTSTART:20250104T140000Z",
+            "UID:daily\nSUMMARY:Daily\nRECURRENCE-ID:20250105T090000Z\nDTSTART:20250105T090000Z\nSTATUS:CANCELLED",
+        ]),
+        Zone::Local,
+    )
+    .unwrap();
+    let next = |after| calendar.next_event(&utc(after)).unwrap();
+
+    assert_eq!(
+        utc("2025-01-04T14:00:00Z"),
+        next("2025-01-01T09:00:00Z").start
+    );
+    assert_eq!("Daily (moved)", next("2025-01-01T09:00:00Z").summary);
+    assert_eq!(
+        utc("2025-01-06T09:00:00Z"),
+        next("2025-01-04T14:00:00Z").start
+    )
+}
+
+#[test]
+fn test_calendar_0005() {
+    for event in [
+        "UID:a\nSUMMARY:No start",
+        "UID:a\nDTSTART:2025-01-01",
+        "UID:a\nDTSTART:20250101T090000Z\nRRULE:FREQ=HOURLY",
+        "UID:a\nDTSTART:20250101T090000Z\nRRULE:INTERVAL=2",
+        "UID:a\nDTSTART:20250101T090000Z\nRRULE:FREQ=DAILY;BYSETPOS=1",
+        "UID:a\nDTSTART:20250101T090000Z\nRRULE:FREQ=WEEKLY;BYDAY=XX",
+    ] {
+        assert!(
+            matches!(
+                Calendar::parse(&ics(&[event]), Zone::Local),
+                Err(NeedleError::InvalidCalendar(_))
+            ),
+            "{}",
+            event
+        )
+    }
+}
+
+#[test]
+fn test_calendar_0006() {
+    let path = std::env::temp_dir().join("needle_test_calendar_0006.ics");
+    let now = Instant::now();
+
+    fs::write(
+        &path,
+        ics(&["UID:a\nSUMMARY:First\nDTSTART:20250101T090000Z"]),
+    )
+    .unwrap();
+
+    let mut source = CalendarSource::new(&path, Zone::Local).unwrap();
+
+    assert_eq!("First", source.calendar().events()[0].summary);
+    assert!(!source.poll(now).unwrap());
+
+    fs::write(
+        &path,
+        ics(&["UID:a\nSUMMARY:Second\nDTSTART:20250101T090000Z"]),
+    )
+    .unwrap();
+    // Force a different modification time on filesystems with coarse timestamps
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+
+    // Not checked again within the reload interval
+    assert!(!source.poll(now + Duration::from_millis(500)).unwrap());
+    assert!(source.poll(now + Duration::from_secs(1)).unwrap());
+    assert_eq!("Second", source.calendar().events()[0].summary);
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,291 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::duration::deserialize_duration;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChessSide {
+    White,
+    Black,
+}
+
+/// Time control applied on every move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChessIncrement {
+    #[default]
+    None,
+    /// Added after each move
+    Fischer(#[serde(deserialize_with = "deserialize_duration")] Duration),
+    /// Time used for a move is given back up to the delay
+    Bronstein(#[serde(deserialize_with = "deserialize_duration")] Duration),
+}
+
+/// Time control of `OpMode::ChessClock`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChessClock {
+    /// Initial time of each side
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub time: Duration,
+    #[serde(default)]
+    pub increment: ChessIncrement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChessStatus {
+    /// Side whose clock is running
+    pub active: Option<ChessSide>,
+    pub white: Duration,
+    pub black: Duration,
+    pub white_moves: u32,
+    pub black_moves: u32,
+    /// Side which ran out of time
+    pub flagged: Option<ChessSide>,
+}
+
+/// Serializable state of a game, see `TimeSnapshot::chess`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChessSnapshot {
+    /// Time left at the start of the current turn
+    pub white: Duration,
+    pub black: Duration,
+    pub white_moves: u32,
+    pub black_moves: u32,
+    pub active: Option<ChessSide>,
+    /// Time into the current turn when the snapshot was saved
+    pub turn: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChessState {
+    remaining: [Duration; 2],
+    moves: [u32; 2],
+    active: Option<ChessSide>,
+    turn_start: Instant,
+}
+
+impl ChessSide {
+    #[inline]
+    pub fn opponent(&self) -> Self {
+        match self {
+            Self::White => Self::Black,
+            Self::Black => Self::White,
+        }
+    }
+
+    #[inline]
+    const fn index(&self) -> usize {
+        match self {
+            Self::White => 0,
+            Self::Black => 1,
+        }
+    }
+}
+
+impl ChessState {
+    pub(crate) fn new(clock: &ChessClock, now: Instant) -> Self {
+        Self {
+            remaining: [clock.time; 2],
+            moves: [0; 2],
+            active: None,
+            turn_start: now,
+        }
+    }
+
+    /// Restores a game whose current turn has run for `snapshot.turn` and
+    /// `downtime` since
+    pub(crate) fn restore(snapshot: &ChessSnapshot, downtime: Duration, now: Instant) -> Self {
+        let mut state = Self {
+            remaining: [snapshot.white, snapshot.black],
+            moves: [snapshot.white_moves, snapshot.black_moves],
+            active: snapshot.active,
+            turn_start: now,
+        };
+
+        if snapshot.active.is_some() {
+            state.suspend(snapshot.turn.saturating_add(downtime));
+        }
+
+        state
+    }
+
+    pub(crate) fn snapshot(&self, now: Instant) -> ChessSnapshot {
+        ChessSnapshot {
+            white: self.remaining[0],
+            black: self.remaining[1],
+            white_moves: self.moves[0],
+            black_moves: self.moves[1],
+            active: self.active,
+            turn: match self.active {
+                Some(_) => now - self.turn_start,
+                None => Duration::new(0, 0),
+            },
+        }
+    }
+
+    /// Ends the turn of the running side and starts the opponent's clock.
+    /// White's clock is started if neither side is running yet.
+    /// Returns the side whose turn has started.
+    pub(crate) fn toggle(&mut self, clock: &ChessClock, now: Instant) -> Option<ChessSide> {
+        if self.flagged(now).is_some() {
+            return None;
+        }
+
+        let next = match self.active {
+            Some(side) => {
+                let used = now - self.turn_start;
+                let remaining = self.remaining[side.index()] - used;
+                let bonus = match clock.increment {
+                    ChessIncrement::None => Duration::new(0, 0),
+                    ChessIncrement::Fischer(increment) => increment,
+                    ChessIncrement::Bronstein(delay) => used.min(delay),
+                };
+
+                self.remaining[side.index()] = remaining + bonus;
+                self.moves[side.index()] += 1;
+
+                side.opponent()
+            }
+            None => ChessSide::White,
+        };
+
+        self.active = Some(next);
+        self.turn_start = now;
+
+        Some(next)
+    }
+
+    pub(crate) fn status(&self, now: Instant) -> ChessStatus {
+        let mut remaining = self.remaining;
+
+        if let Some(side) = self.active {
+            remaining[side.index()] = remaining[side.index()].saturating_sub(now - self.turn_start);
+        }
+
+        ChessStatus {
+            active: self.active,
+            white: remaining[0],
+            black: remaining[1],
+            white_moves: self.moves[0],
+            black_moves: self.moves[1],
+            flagged: self.flagged(now),
+        }
+    }
+
+    /// Counts time the machine was suspended against the running side
+    pub(crate) fn suspend(&mut self, duration: Duration) {
+        let Some(side) = self.active else {
+            return;
+        };
+
+        match self.turn_start.checked_sub(duration) {
+            Some(turn_start) => self.turn_start = turn_start,
+            None => {
+                self.remaining[side.index()] = self.remaining[side.index()].saturating_sub(duration)
+            }
+        }
+    }
+
+    fn flagged(&self, now: Instant) -> Option<ChessSide> {
+        self.active
+            .filter(|side| now - self.turn_start >= self.remaining[side.index()])
+    }
+}
+
+impl Default for ChessClock {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(5 * 60),
+            increment: ChessIncrement::None,
+        }
+    }
+}
+
+impl Display for ChessSide {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::White => write!(f, "White"),
+            Self::Black => write!(f, "Black"),
+        }
+    }
+}
+
+#[test]
+fn test_chess_0001() {
+    let clock = ChessClock {
+        time: Duration::from_secs(60),
+        increment: ChessIncrement::Fischer(Duration::from_secs(2)),
+    };
+    let start = Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let mut state = ChessState::new(&clock, start);
+
+    assert_eq!(Some(ChessSide::White), state.toggle(&clock, at(0)));
+    assert_eq!(Some(ChessSide::Black), state.toggle(&clock, at(10)));
+    assert_eq!(Some(ChessSide::White), state.toggle(&clock, at(15)));
+
+    let status = state.status(at(20));
+
+    assert_eq!(Some(ChessSide::White), status.active);
+    assert_eq!(Duration::from_secs(60 - 10 + 2 - 5), status.white);
+    assert_eq!(Duration::from_secs(60 - 5 + 2), status.black);
+    assert_eq!((1, 1), (status.white_moves, status.black_moves));
+    assert_eq!(None, status.flagged)
+}
+
+#[test]
+fn test_chess_0002() {
+    let clock = ChessClock {
+        time: Duration::from_secs(60),
+        increment: ChessIncrement::Bronstein(Duration::from_secs(3)),
+    };
+    let start = Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let mut state = ChessState::new(&clock, start);
+
+    state.toggle(&clock, at(0));
+    // Fast move is fully refunded
+    state.toggle(&clock, at(2));
+    // Slow move is refunded up to the delay
+    state.toggle(&clock, at(12));
+
+    let status = state.status(at(12));
+
+    assert_eq!(Duration::from_secs(60), status.white);
+    assert_eq!(Duration::from_secs(60 - 10 + 3), status.black);
+
+    let status = state.status(at(72));
+
+    assert_eq!(Some(ChessSide::White), status.flagged);
+    assert_eq!(Duration::new(0, 0), status.white);
+    // No more moves after a flag fall
+    assert_eq!(None, state.toggle(&clock, at(73)))
+}
+
+#[test]
+fn test_chess_0003() {
+    let clock = ChessClock {
+        time: Duration::from_secs(60),
+        increment: ChessIncrement::Bronstein(Duration::from_secs(3)),
+    };
+    let start = Instant::now() + Duration::from_secs(60);
+    let at = |secs| start + Duration::from_secs(secs);
+    let mut state = ChessState::new(&clock, start);
+
+    // Nothing is running yet
+    state.suspend(Duration::from_secs(5));
+    assert_eq!(Duration::from_secs(60), state.status(at(0)).white);
+
+    state.toggle(&clock, at(0));
+    state.suspend(Duration::from_secs(5));
+    assert_eq!(Duration::from_secs(55), state.status(at(0)).white);
+    assert_eq!(Duration::from_secs(60), state.status(at(0)).black);
+
+    // Time suspended is part of the move
+    state.toggle(&clock, at(1));
+    assert_eq!(Duration::from_secs(57), state.status(at(1)).white)
+}
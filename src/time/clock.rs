@@ -0,0 +1,116 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use chrono::{DateTime, Utc};
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+pub trait ClockSource: Debug + Send + Sync {
+    /// Monotonic time used for timers
+    fn now(&self) -> Instant;
+    /// Wall-clock time used for clock display
+    fn utc_now(&self) -> DateTime<Utc>;
+    /// Monotonic time which keeps counting while suspended, unlike `now`.
+    /// Without it a resume from suspend is seen as a wall-clock jump.
+    fn boot_time(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl ClockSource for SystemClock {
+    #[inline]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    #[inline]
+    fn utc_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn boot_time(&self) -> Option<Duration> {
+        let mut time = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+
+        // SAFETY: `time` is a valid timespec to write to
+        match unsafe { libc::clock_gettime(libc::CLOCK_BOOTTIME, &mut time) } {
+            0 => Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MockClockState {
+    instant: Instant,
+    wall: DateTime<Utc>,
+    boot: Duration,
+}
+
+/// Manually advanced clock. Clones share the same state, so a handle can be
+/// kept to drive a `Time` which owns another clone.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockClockState>>,
+}
+
+impl MockClock {
+    pub fn new(wall: DateTime<Utc>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockClockState {
+                instant: Instant::now(),
+                wall,
+                boot: Duration::new(0, 0),
+            })),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state();
+
+        state.instant += duration;
+        state.wall += duration;
+        state.boot += duration;
+    }
+
+    /// Passes `duration` as if the machine had been suspended, so the
+    /// monotonic time stands still
+    pub fn suspend(&self, duration: Duration) {
+        let mut state = self.state();
+
+        state.wall += duration;
+        state.boot += duration;
+    }
+
+    pub fn set_wall(&self, wall: DateTime<Utc>) {
+        self.state().wall = wall;
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockClockState> {
+        // State is only ever replaced wholesale, so a poisoned lock is still usable
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl ClockSource for MockClock {
+    fn now(&self) -> Instant {
+        self.state().instant
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        self.state().wall
+    }
+
+    fn boot_time(&self) -> Option<Duration> {
+        Some(self.state().boot)
+    }
+}
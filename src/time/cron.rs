@@ -0,0 +1,213 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+#[cfg(test)]
+use super::utc;
+use super::Zone;
+use crate::{NeedleErr, NeedleError};
+use chrono::{DateTime, Local, SubsecRound, TimeZone, Utc};
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Cron expression counted down to by `OpMode::Cron`.
+///
+/// Takes 5 fields (`minute hour day month weekday`) or 6 fields with
+/// leading seconds. Weekdays are 0-7 with both 0 and 7 being Sunday.
+/// A `CRON_TZ=<timezone>` prefix overrides the timezone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "CronSpec", into = "CronSpec")]
+pub struct CronSchedule {
+    expression: String,
+    timezone: Zone,
+    cron: Cron,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CronSpec {
+    expression: String,
+    #[serde(default)]
+    timezone: Zone,
+}
+
+impl CronSchedule {
+    const DEFAULT_EXPRESSION: &'static str = "0 0 * * *";
+    const TIMEZONE_PREFIX: &'static str = "CRON_TZ=";
+
+    /// Parses `expression` evaluated in `timezone`
+    pub fn parse(expression: &str, timezone: Zone) -> NeedleErr<Self> {
+        let invalid = |reason: &str| {
+            NeedleError::InvalidCronExpression(format!("\"{}\": {}", expression, reason).into())
+        };
+        let (timezone, fields) = match expression.trim().strip_prefix(Self::TIMEZONE_PREFIX) {
+            Some(rest) => {
+                let (name, fields) = rest
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| invalid("fields are missing"))?;
+
+                let timezone = Zone::parse(name)
+                    .map_err(|_| invalid(&format!("unknown timezone \"{}\"", name)))?;
+
+                (timezone, fields.trim())
+            }
+            None => (timezone, expression.trim()),
+        };
+
+        match fields.split_whitespace().count() {
+            5 | 6 => Ok(()),
+            count => Err(invalid(&format!("expected 5 or 6 fields, found {}", count))),
+        }?;
+
+        let cron = Cron::new(fields)
+            .with_seconds_optional()
+            .parse()
+            .map_err(|err| invalid(&err.to_string()))?;
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            timezone,
+            cron,
+        })
+    }
+
+    #[inline]
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    #[inline]
+    pub fn timezone(&self) -> Zone {
+        self.timezone
+    }
+
+    /// First occurrence strictly after `after`. Skipped and repeated wall-clock
+    /// times around DST transitions are resolved by `croner`.
+    pub fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        // Occurrences are whole seconds, searching from a whole second
+        // avoids skipping the next one
+        let after = after.trunc_subsecs(0);
+
+        match self.timezone {
+            Zone::Local => self.next_in(&after.with_timezone(&Local)),
+            Zone::Named(tz) => self.next_in(&after.with_timezone(&tz)),
+            Zone::Fixed(offset) => self.next_in(&after.with_timezone(&offset)),
+        }
+    }
+
+    fn next_in<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+        self.cron
+            .find_next_occurrence(after, false)
+            .ok()
+            .map(|next| next.with_timezone(&Utc))
+    }
+}
+
+impl Default for CronSchedule {
+    fn default() -> Self {
+        Self {
+            expression: Self::DEFAULT_EXPRESSION.to_string(),
+            timezone: Zone::Local,
+            cron: Cron::new(Self::DEFAULT_EXPRESSION)
+                .parse()
+                .expect("default cron expression is valid"),
+        }
+    }
+}
+
+impl PartialEq for CronSchedule {
+    fn eq(&self, other: &Self) -> bool {
+        self.expression == other.expression && self.timezone == other.timezone
+    }
+}
+
+impl TryFrom<CronSpec> for CronSchedule {
+    type Error = NeedleError;
+
+    fn try_from(spec: CronSpec) -> NeedleErr<Self> {
+        Self::parse(&spec.expression, spec.timezone)
+    }
+}
+
+impl From<CronSchedule> for CronSpec {
+    fn from(schedule: CronSchedule) -> Self {
+        Self {
+            expression: schedule.expression,
+            timezone: schedule.timezone,
+        }
+    }
+}
+
+impl Display for CronSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.expression, self.timezone)
+    }
+}
+
+#[test]
+fn test_cron_0001() {
+    let utc_zone = Zone::parse("UTC").unwrap();
+    // Wednesday
+    let now = utc("2025-01-01T12:34:56.789Z");
+    let next = |expression| {
+        CronSchedule::parse(expression, utc_zone)
+            .unwrap()
+            .next_after(&now)
+    };
+
+    assert_eq!(Some(utc("2025-01-01T12:35:00Z")), next("* * * * *"));
+    assert_eq!(Some(utc("2025-01-01T12:34:57Z")), next("* * * * * *"));
+    assert_eq!(Some(utc("2025-01-01T12:45:00Z")), next("*/15 * * * *"));
+    // Weekdays at 09:30, Sunday is 0
+    assert_eq!(Some(utc("2025-01-02T09:30:00Z")), next("30 9 * * 1-5"));
+    assert_eq!(Some(utc("2025-01-05T00:00:00Z")), next("0 0 * * 0"));
+    assert_eq!(Some(utc("2025-01-05T00:00:00Z")), next("0 0 * * 7"));
+    assert_eq!(Some(utc("2025-02-01T00:00:00Z")), next("0 0 1 * *"));
+    assert_eq!(
+        Some(utc("2025-01-01T15:30:00Z")),
+        next("CRON_TZ=Asia/Tokyo 30 0 * * *")
+    );
+}
+
+#[test]
+fn test_cron_0002() {
+    let zone = Zone::parse("America/New_York").unwrap();
+    let schedule = CronSchedule::parse("30 2 * * *", zone).unwrap();
+
+    // 02:30 doesn't exist on 2025-03-09 in New York
+    let next = schedule.next_after(&utc("2025-03-09T05:00:00Z")).unwrap();
+
+    assert!(next > utc("2025-03-09T05:00:00Z"));
+    assert!(next <= utc("2025-03-10T06:30:00Z"));
+    assert_eq!(
+        Some(utc("2025-03-10T06:30:00Z")),
+        schedule.next_after(&next)
+    );
+}
+
+#[test]
+fn test_cron_0003() {
+    for expression in [
+        "",
+        "* * * *",
+        "0 0 0 * * * *",
+        "61 * * * *",
+        "* * * * 8",
+        "CRON_TZ=Mars/Olympus 0 0 * * *",
+        "CRON_TZ=UTC",
+    ] {
+        assert!(
+            matches!(
+                CronSchedule::parse(expression, Zone::Local),
+                Err(NeedleError::InvalidCronExpression(_))
+            ),
+            "{}",
+            expression
+        )
+    }
+
+    let schedule = CronSchedule::parse("0 9 * * 1-5", Zone::parse("+09:00").unwrap()).unwrap();
+    let parsed: CronSchedule = toml::from_str(&toml::to_string(&schedule).unwrap()).unwrap();
+
+    assert_eq!(schedule, parsed);
+    assert!(toml::from_str::<CronSchedule>("expression = \"0 9 * *\"").is_err())
+}
@@ -0,0 +1,169 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{ClockSource, Zone};
+use chrono::{DateTime, FixedOffset, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// Break in the clocks noticed by `Time::poll_events`. Anything rendered from
+/// the wall clock is stale afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Discontinuity {
+    /// Wall clock set forward or backward by the delta
+    WallClockJump(TimeDelta),
+    /// UTC offset of the timezone changed, e.g. by DST
+    OffsetChanged { from: FixedOffset, to: FixedOffset },
+    /// Machine resumed after being suspended for the duration
+    Suspended(Duration),
+}
+
+/// Whether running timers count the time the machine was suspended.
+///
+/// Only applies where suspend is detected, see `ClockSource::boot_time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SuspendPolicy {
+    /// Timers pause while suspended
+    #[default]
+    Exclude,
+    /// Timers keep counting while suspended, as a kitchen timer would
+    Include,
+}
+
+/// Clocks read at the last check
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClockWatch {
+    instant: Instant,
+    wall: DateTime<Utc>,
+    boot: Option<Duration>,
+    offset: FixedOffset,
+}
+
+impl ClockWatch {
+    /// Drift between the clocks tolerated between two checks
+    const THRESHOLD: Duration = Duration::from_secs(2);
+
+    pub fn new(clock: &dyn ClockSource, zone: Zone) -> Self {
+        let wall = clock.utc_now();
+
+        Self {
+            instant: clock.now(),
+            wall,
+            boot: clock.boot_time(),
+            offset: Self::offset(zone, &wall),
+        }
+    }
+
+    /// Discontinuities since the last check
+    pub fn check(&mut self, clock: &dyn ClockSource, zone: Zone) -> Vec<Discontinuity> {
+        let last = std::mem::replace(self, Self::new(clock, zone));
+        let elapsed = self.instant.saturating_duration_since(last.instant);
+        let suspended = match (last.boot, self.boot) {
+            (Some(last), Some(boot)) => boot.saturating_sub(last).saturating_sub(elapsed),
+            _ => Duration::new(0, 0),
+        };
+        let expected = TimeDelta::from_std(elapsed + suspended).unwrap_or(TimeDelta::MAX);
+        let jump = (self.wall - last.wall) - expected;
+        let threshold = TimeDelta::from_std(Self::THRESHOLD).unwrap_or_default();
+        let mut discontinuities = vec![];
+
+        if suspended >= Self::THRESHOLD {
+            discontinuities.push(Discontinuity::Suspended(suspended));
+        }
+        if jump.abs() >= threshold {
+            discontinuities.push(Discontinuity::WallClockJump(jump));
+        }
+        if self.offset != last.offset {
+            discontinuities.push(Discontinuity::OffsetChanged {
+                from: last.offset,
+                to: self.offset,
+            });
+        }
+
+        discontinuities
+    }
+
+    /// Forgets the UTC offset of the last check, e.g. when the timezone is
+    /// replaced on purpose
+    pub fn reset_offset(&mut self, zone: Zone) {
+        self.offset = Self::offset(zone, &self.wall)
+    }
+
+    fn offset(zone: Zone, time: &DateTime<Utc>) -> FixedOffset {
+        let seconds = (zone.to_local(time) - time.naive_utc()).num_seconds();
+
+        FixedOffset::east_opt(seconds as i32).unwrap_or(FixedOffset::east_opt(0).unwrap())
+    }
+}
+
+impl Display for Discontinuity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WallClockJump(delta) => write!(f, "WallClockJump ({}s)", delta.num_seconds()),
+            Self::OffsetChanged { from, to } => write!(f, "OffsetChanged ({} -> {})", from, to),
+            Self::Suspended(duration) => write!(f, "Suspended ({}s)", duration.as_secs()),
+        }
+    }
+}
+
+#[test]
+fn test_discontinuity_0001() {
+    use super::MockClock;
+    use chrono::TimeZone;
+
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 12, 34, 56).unwrap());
+    let zone = Zone::parse("UTC").unwrap();
+    let mut watch = ClockWatch::new(&clock, zone);
+
+    clock.advance(Duration::from_secs(3_600));
+    assert!(watch.check(&clock, zone).is_empty());
+
+    // Jitter below the threshold is ignored
+    clock.set_wall(clock.utc_now() + TimeDelta::seconds(1));
+    assert!(watch.check(&clock, zone).is_empty());
+
+    clock.set_wall(clock.utc_now() - TimeDelta::minutes(5));
+    assert_eq!(
+        vec![Discontinuity::WallClockJump(TimeDelta::minutes(-5))],
+        watch.check(&clock, zone)
+    );
+
+    clock.suspend(Duration::from_secs(600));
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(
+        vec![Discontinuity::Suspended(Duration::from_secs(600))],
+        watch.check(&clock, zone)
+    );
+}
+
+#[test]
+fn test_discontinuity_0002() {
+    use super::MockClock;
+    use chrono::TimeZone;
+
+    // An hour before DST starts in New York
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2025, 3, 9, 6, 0, 0).unwrap());
+    let zone = Zone::parse("America/New_York").unwrap();
+    let mut watch = ClockWatch::new(&clock, zone);
+
+    clock.advance(Duration::from_secs(30 * 60));
+    assert!(watch.check(&clock, zone).is_empty());
+
+    clock.advance(Duration::from_secs(30 * 60));
+    assert_eq!(
+        vec![Discontinuity::OffsetChanged {
+            from: FixedOffset::west_opt(5 * 3_600).unwrap(),
+            to: FixedOffset::west_opt(4 * 3_600).unwrap(),
+        }],
+        watch.check(&clock, zone)
+    );
+
+    // Replacing the timezone isn't a discontinuity
+    let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+
+    watch.reset_offset(tokyo);
+    assert!(watch.check(&clock, tokyo).is_empty())
+}
@@ -0,0 +1,364 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{NeedleErr, NeedleError};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::time::Duration;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+const MINUTE_NANOS: u128 = 60 * NANOS_PER_SEC;
+const HOUR_NANOS: u128 = 60 * MINUTE_NANOS;
+const DAY_NANOS: u128 = 24 * HOUR_NANOS;
+
+/// Layout of timer durations for `HourMinSec` and `HourMinSecMSec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DurationFormat {
+    /// Roll hours over into a days field, e.g. `1d 02:05:03`
+    pub days: bool,
+    /// Omit zero leading fields, e.g. `5:03` instead of `00:05:03`
+    pub trim: bool,
+}
+
+/// Parses a human-friendly duration.
+///
+/// | Notation  | Example                        |
+/// |-----------|--------------------------------|
+/// | Units     | `1h30m`, `90s`, `1d 2h`, `1.5h`, `250ms` |
+/// | Clock     | `00:25:00`, `25:00`, `1:02:03.5` |
+/// | ISO 8601  | `PT25M`, `P1DT12H`, `PT0.5S`   |
+pub fn parse_duration(text: &str) -> NeedleErr<Duration> {
+    let trimmed = text.trim();
+    let invalid = |reason: String| -> NeedleError {
+        NeedleError::InvalidDuration(format!("\"{}\": {}", text, reason).into())
+    };
+
+    let nanos = if trimmed.is_empty() {
+        Err("duration is empty".to_string())
+    } else if trimmed.starts_with(['P', 'p']) {
+        parse_iso8601(&trimmed[1..])
+    } else if trimmed.contains(':') {
+        parse_clock(trimmed)
+    } else {
+        parse_units(trimmed)
+    }
+    .map_err(invalid)?;
+
+    let secs = u64::try_from(nanos / NANOS_PER_SEC).map_err(|_| invalid("too long".into()))?;
+
+    Ok(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+}
+
+/// Deserializes a duration from `parse_duration` notation, whole seconds or
+/// serde's `{ secs, nanos }` representation.
+pub(crate) fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_in_unit(deserializer, 1)
+}
+
+/// Same as `deserialize_duration` with bare integers being whole minutes,
+/// e.g. `work = 25` for a 25-minute Pomodoro work phase.
+pub(crate) fn deserialize_minutes<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_in_unit(deserializer, 60)
+}
+
+fn deserialize_in_unit<'de, D>(deserializer: D, unit_secs: u64) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Text(String),
+        Secs(u64),
+        Raw { secs: u64, nanos: u32 },
+    }
+
+    match Repr::deserialize(deserializer)? {
+        Repr::Text(text) => parse_duration(&text).map_err(serde::de::Error::custom),
+        Repr::Secs(count) => count
+            .checked_mul(unit_secs)
+            .map(Duration::from_secs)
+            .ok_or_else(|| serde::de::Error::custom(format!("{}: too long", count))),
+        Repr::Raw { secs, nanos } => Ok(Duration::new(secs, nanos)),
+    }
+}
+
+/// Writes a duration in `parse_duration` notation, e.g. `90s` or `2.5s`
+pub(crate) fn duration_to_config_str(duration: &Duration) -> String {
+    match duration.subsec_nanos() {
+        0 => format!("{}s", duration.as_secs()),
+        nanos => format!(
+            "{}.{}s",
+            duration.as_secs(),
+            format!("{:09}", nanos).trim_end_matches('0')
+        ),
+    }
+}
+
+/// Splits a leading decimal number such as `1.5` off `text`.
+fn take_number(text: &str) -> Option<(&str, &str, &str)> {
+    let int_len = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (int, rest) = text.split_at(int_len);
+
+    match rest.strip_prefix(['.', ',']) {
+        Some(rest) => {
+            let frac_len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (frac, rest) = rest.split_at(frac_len);
+
+            if int.is_empty() && frac.is_empty() {
+                None
+            } else {
+                Some((int, frac, rest))
+            }
+        }
+        None if int.is_empty() => None,
+        None => Some((int, "", rest)),
+    }
+}
+
+fn scale(int: &str, frac: &str, unit: u128) -> Result<u128, String> {
+    let overflow = || "too long".to_string();
+    let int = if int.is_empty() {
+        0
+    } else {
+        int.parse::<u128>().map_err(|_| overflow())?
+    };
+    let mut nanos = int.checked_mul(unit).ok_or_else(overflow)?;
+    let mut place = unit;
+
+    for digit in frac.chars() {
+        place /= 10;
+        nanos += (digit as u128 - '0' as u128) * place;
+    }
+
+    Ok(nanos)
+}
+
+fn parse_units(text: &str) -> Result<u128, String> {
+    let mut rest = text;
+    let mut nanos = 0u128;
+    let mut seen: Vec<&str> = vec![];
+
+    loop {
+        rest = rest.trim_start();
+
+        if rest.is_empty() {
+            return Ok(nanos);
+        }
+
+        let position = text.len() - rest.len();
+        let (int, frac, after) =
+            take_number(rest).ok_or_else(|| format!("expected a number at {}", position))?;
+        let number = &rest[..rest.len() - after.len()];
+        let after = after.trim_start();
+        let unit_position = text.len() - after.len();
+        let unit_len = after
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        let (name, scale_nanos) = match unit.to_ascii_lowercase().as_str() {
+            "" => {
+                return Err(format!(
+                    "missing unit after \"{}\" (expected d, h, m, s or ms)",
+                    number
+                ))
+            }
+            "d" | "day" | "days" => ("d", DAY_NANOS),
+            "h" | "hr" | "hrs" | "hour" | "hours" => ("h", HOUR_NANOS),
+            "m" | "min" | "mins" | "minute" | "minutes" => ("m", MINUTE_NANOS),
+            "s" | "sec" | "secs" | "second" | "seconds" => ("s", NANOS_PER_SEC),
+            "ms" => ("ms", NANOS_PER_SEC / 1_000),
+            _ => {
+                return Err(format!(
+                    "unknown unit \"{}\" at {} (expected d, h, m, s or ms)",
+                    unit, unit_position
+                ))
+            }
+        };
+
+        if seen.contains(&name) {
+            return Err(format!("unit \"{}\" is given more than once", name));
+        }
+        seen.push(name);
+
+        nanos = nanos
+            .checked_add(scale(int, frac, scale_nanos)?)
+            .ok_or_else(|| "too long".to_string())?;
+        rest = after;
+    }
+}
+
+fn parse_clock(text: &str) -> Result<u128, String> {
+    let fields = text.split(':').collect::<Vec<_>>();
+    let (hours, minutes, seconds) = match fields[..] {
+        [minutes, seconds] => ("0", minutes, seconds),
+        [hours, minutes, seconds] => (hours, minutes, seconds),
+        _ => return Err("expected \"HH:MM:SS\" or \"MM:SS\"".to_string()),
+    };
+    let integer = |field: &str, name: &str| -> Result<u128, String> {
+        if field.is_empty() || !field.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("invalid {} \"{}\"", name, field));
+        }
+
+        field.parse::<u128>().map_err(|_| "too long".to_string())
+    };
+    let hours = integer(hours, "hours")?;
+    let minutes = integer(minutes, "minutes")?;
+    let seconds = match take_number(seconds) {
+        Some((int, frac, "")) if !int.is_empty() => scale(int, frac, NANOS_PER_SEC)?,
+        _ => return Err(format!("invalid seconds \"{}\"", seconds)),
+    };
+
+    if fields.len() == 3 && minutes >= 60 {
+        return Err(format!("minutes must be less than 60 (got {})", minutes));
+    }
+    if seconds >= MINUTE_NANOS {
+        return Err("seconds must be less than 60".to_string());
+    }
+
+    hours
+        .checked_mul(HOUR_NANOS)
+        .and_then(|nanos| nanos.checked_add(minutes.checked_mul(MINUTE_NANOS)?))
+        .and_then(|nanos| nanos.checked_add(seconds))
+        .ok_or_else(|| "too long".to_string())
+}
+
+fn parse_iso8601(text: &str) -> Result<u128, String> {
+    let (date, time) = match text.split_once(['T', 't']) {
+        Some((_, "")) => return Err("no time given after 'T'".to_string()),
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+
+    if date.is_empty() && time.is_none() {
+        return Err("no duration given after 'P'".to_string());
+    }
+
+    let mut nanos = 0u128;
+
+    for (part, is_time) in [(date, false), (time.unwrap_or(""), true)] {
+        let mut rest = part;
+        let mut order = 0;
+
+        while !rest.is_empty() {
+            let (int, frac, after) = take_number(rest)
+                .ok_or_else(|| format!("expected a number before \"{}\"", rest))?;
+            let mut chars = after.chars();
+            let designator = chars
+                .next()
+                .ok_or_else(|| format!("missing designator after \"{}\"", int))?
+                .to_ascii_uppercase();
+            let (rank, unit) = match (is_time, designator) {
+                (false, 'W') => (1, 7 * DAY_NANOS),
+                (false, 'D') => (2, DAY_NANOS),
+                (false, 'Y' | 'M') => {
+                    return Err("years and months have no fixed length".to_string())
+                }
+                (true, 'H') => (1, HOUR_NANOS),
+                (true, 'M') => (2, MINUTE_NANOS),
+                (true, 'S') => (3, NANOS_PER_SEC),
+                (_, designator) => {
+                    return Err(format!(
+                        "unexpected designator '{}' in {} part",
+                        designator,
+                        if is_time { "time" } else { "date" }
+                    ))
+                }
+            };
+
+            if rank <= order {
+                return Err(format!("designator '{}' is out of order", designator));
+            }
+            order = rank;
+
+            nanos = nanos
+                .checked_add(scale(int, frac, unit)?)
+                .ok_or_else(|| "too long".to_string())?;
+            rest = chars.as_str();
+        }
+    }
+
+    Ok(nanos)
+}
+
+#[test]
+fn test_duration_0001() {
+    let expected = [
+        ("1h30m", Duration::from_secs(5_400)),
+        ("90s", Duration::from_secs(90)),
+        ("1d 2h", Duration::from_secs(93_600)),
+        ("1.5h", Duration::from_secs(5_400)),
+        ("2 minutes 5 seconds", Duration::from_secs(125)),
+        ("250ms", Duration::from_millis(250)),
+        ("1m0.125s", Duration::from_millis(60_125)),
+        ("00:25:00", Duration::from_secs(1_500)),
+        ("25:00", Duration::from_secs(1_500)),
+        ("100:00:01", Duration::from_secs(360_001)),
+        ("1:02:03.5", Duration::from_millis(3_723_500)),
+        ("PT25M", Duration::from_secs(1_500)),
+        ("P1DT12H", Duration::from_secs(129_600)),
+        ("P2W", Duration::from_secs(14 * 86_400)),
+        ("pt0,5s", Duration::from_millis(500)),
+        ("PT1H0.25M", Duration::from_secs(3_615)),
+    ];
+
+    for (text, duration) in expected {
+        assert_eq!(duration, parse_duration(text).unwrap(), "{}", text)
+    }
+}
+
+#[test]
+fn test_duration_0002() {
+    let invalid = [
+        ("", "duration is empty"),
+        ("90", "missing unit after \"90\""),
+        ("1h30x", "unknown unit \"x\" at 4"),
+        ("1h1h", "unit \"h\" is given more than once"),
+        ("h", "expected a number at 0"),
+        ("1:60:00", "minutes must be less than 60"),
+        ("1:00:75", "seconds must be less than 60"),
+        ("1:2:3:4", "expected \"HH:MM:SS\" or \"MM:SS\""),
+        ("P1M", "years and months have no fixed length"),
+        ("PT", "no time given after 'T'"),
+        ("P", "no duration given after 'P'"),
+        ("PT5S1M", "designator 'M' is out of order"),
+        ("PT5D", "unexpected designator 'D' in time part"),
+    ];
+
+    for (text, reason) in invalid {
+        let err = parse_duration(text).unwrap_err().to_string();
+
+        assert!(err.contains(reason), "{}: {}", text, err)
+    }
+}
+
+#[test]
+fn test_duration_0003() {
+    assert!(parse_duration("99999999999999999999999d").is_err());
+    assert!(parse_duration("PT99999999999999999999999H").is_err())
+}
+
+#[test]
+fn test_duration_0004() {
+    for duration in [
+        Duration::new(0, 0),
+        Duration::from_secs(5_400),
+        Duration::from_millis(2_500),
+        Duration::new(1, 1),
+    ] {
+        assert_eq!(
+            duration,
+            parse_duration(&duration_to_config_str(&duration)).unwrap()
+        )
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{AlarmEvent, ChessSide, Discontinuity, Lap, PomodoroPhase};
+use std::fmt::{self, Display, Formatter};
+
+/// Timer state changes reported by `Time::poll_events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerEvent {
+    Started,
+    Paused,
+    Resumed,
+    /// Countdown reached zero
+    Finished,
+    /// Cron or solar schedule reached its next occurrence
+    Occurrence,
+    /// Calendar event started
+    EventStarted,
+    /// Last split of a run was recorded
+    RunCompleted,
+    LapRecorded(Lap),
+    PhaseChanged(PomodoroPhase),
+    /// Index of the segment a sequence moved on to
+    SegmentChanged(usize),
+    /// Side whose clock started running
+    TurnChanged(ChessSide),
+    Alarm(AlarmEvent),
+    /// Clocks jumped, anything derived from the wall clock needs a redraw
+    ClockChanged(Discontinuity),
+}
+
+impl Display for TimerEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Started => write!(f, "Started"),
+            Self::Paused => write!(f, "Paused"),
+            Self::Resumed => write!(f, "Resumed"),
+            Self::Finished => write!(f, "Finished"),
+            Self::Occurrence => write!(f, "Occurrence"),
+            Self::EventStarted => write!(f, "EventStarted"),
+            Self::RunCompleted => write!(f, "RunCompleted"),
+            Self::LapRecorded(lap) => write!(f, "LapRecorded ({:?})", lap.total),
+            Self::PhaseChanged(phase) => write!(f, "PhaseChanged ({})", phase),
+            Self::SegmentChanged(index) => write!(f, "SegmentChanged ({})", index),
+            Self::TurnChanged(side) => write!(f, "TurnChanged ({})", side),
+            Self::Alarm(event) => write!(f, "Alarm ({:?})", event),
+            Self::ClockChanged(discontinuity) => write!(f, "ClockChanged ({})", discontinuity),
+        }
+    }
+}
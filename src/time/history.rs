@@ -0,0 +1,340 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{Lap, Zone};
+use crate::{NeedleConfig, NeedleErr, NeedleError};
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Completed timer run, see `Time::finish_session`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Name of the `OpMode` the run was timed in
+    pub mode: String,
+    /// Name of the timer in `TimerManager`, if any
+    #[serde(default)]
+    pub label: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Time counted by the timer
+    pub elapsed: Duration,
+    /// Time spent paused between `started_at` and `ended_at`
+    pub paused: Duration,
+    #[serde(default)]
+    pub laps: Vec<Lap>,
+}
+
+/// Sessions appended to `history.toml` next to `config.toml`, oldest first.
+///
+/// Nothing is recorded automatically: the host appends each completed run
+/// with `append`, e.g. from `Time::finish_session` once `poll_events`
+/// reports `TimerEvent::Finished` or `TimerEvent::RunCompleted`, or when the
+/// user stops the timer.
+///
+/// Queries return the matching sessions as a new `SessionHistory`, so they
+/// can be chained and exported, e.g.
+/// `history.with_label("build").in_week(today, Zone::Local).to_csv()`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionHistory {
+    #[serde(default)]
+    pub sessions: Vec<Session>,
+}
+
+/// `Session` with durations in seconds, as exported to CSV and JSON
+#[derive(Serialize)]
+struct ExportedSession<'a, L> {
+    mode: &'a str,
+    label: &'a str,
+    started_at: String,
+    ended_at: String,
+    elapsed: f64,
+    paused: f64,
+    laps: L,
+}
+
+impl SessionHistory {
+    const HISTORY_FILE: &'static str = "history.toml";
+
+    /// Returns an empty history if nothing has been recorded
+    pub fn load() -> NeedleErr<Self> {
+        Self::load_from(&Self::history_file(false)?)
+    }
+
+    pub fn load_from(path: &Path) -> NeedleErr<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let history = match fs::read_to_string(path) {
+            Ok(history) => Ok(history),
+            Err(err) => Err(NeedleError::FailedToReadHistory(err.into())),
+        }?;
+
+        match toml::from_str(&history) {
+            Ok(history) => Ok(history),
+            Err(err) => Err(NeedleError::FailedToReadHistory(err.into())),
+        }
+    }
+
+    /// Appends `session` to the history file without reading it back
+    pub fn append(session: &Session) -> NeedleErr<()> {
+        Self::append_to(&Self::history_file(true)?, session)
+    }
+
+    pub fn append_to(path: &Path, session: &Session) -> NeedleErr<()> {
+        // An array of tables, so each entry appended is valid on its own
+        let entry = Self {
+            sessions: vec![session.clone()],
+        };
+        let entry = match toml::to_string(&entry) {
+            Ok(entry) => Ok(entry),
+            Err(err) => Err(NeedleError::FailedToWriteHistory(err.into())),
+        }?;
+        let file = match OpenOptions::new().append(true).create(true).open(path) {
+            Ok(file) => Ok(file),
+            Err(err) => Err(NeedleError::FailedToWriteHistory(err.into())),
+        }?;
+        let mut buf_writer = BufWriter::new(file);
+
+        match writeln!(buf_writer, "{}", entry) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(NeedleError::FailedToWriteHistory(err.into())),
+        }
+    }
+
+    /// Sessions started on `date` in `zone`
+    pub fn on_day(&self, date: NaiveDate, zone: Zone) -> Self {
+        self.filter(|session| zone.to_local(&session.started_at).date() == date)
+    }
+
+    /// Sessions started in the ISO week, Monday to Sunday, containing `date`
+    /// in `zone`
+    pub fn in_week(&self, date: NaiveDate, zone: Zone) -> Self {
+        let week = date.iso_week();
+
+        self.filter(|session| zone.to_local(&session.started_at).iso_week() == week)
+    }
+
+    /// Sessions started within `from..to`
+    pub fn between(&self, from: &DateTime<Utc>, to: &DateTime<Utc>) -> Self {
+        self.filter(|session| (*from..*to).contains(&session.started_at))
+    }
+
+    pub fn with_label(&self, label: &str) -> Self {
+        self.filter(|session| session.label.as_deref() == Some(label))
+    }
+
+    /// Labels in the order they first appear
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = vec![];
+
+        for label in self.sessions.iter().filter_map(|s| s.label.as_deref()) {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+
+        labels
+    }
+
+    /// Sum of the time counted by every session
+    pub fn total(&self) -> Duration {
+        self.sessions.iter().map(|session| session.elapsed).sum()
+    }
+
+    /// One row per session. Laps are the lap totals in seconds separated
+    /// by `;`.
+    pub fn to_csv(&self) -> NeedleErr<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        for session in &self.sessions {
+            let laps = session
+                .laps
+                .iter()
+                .map(|lap| Self::seconds(&lap.total).to_string())
+                .collect::<Vec<_>>()
+                .join(";");
+
+            if let Err(err) = writer.serialize(Self::export(session, laps)) {
+                return Err(NeedleError::FailedToExportHistory(err.into()));
+            }
+        }
+
+        let csv = match writer.into_inner() {
+            Ok(csv) => Ok(csv),
+            Err(err) => Err(NeedleError::FailedToExportHistory(err.to_string().into())),
+        }?;
+
+        match String::from_utf8(csv) {
+            Ok(csv) => Ok(csv),
+            Err(err) => Err(NeedleError::FailedToExportHistory(err.into())),
+        }
+    }
+
+    /// Array of sessions with the lap totals in seconds
+    pub fn to_json(&self) -> NeedleErr<String> {
+        let sessions = self
+            .sessions
+            .iter()
+            .map(|session| {
+                let laps = session
+                    .laps
+                    .iter()
+                    .map(|lap| Self::seconds(&lap.total))
+                    .collect::<Vec<_>>();
+
+                Self::export(session, laps)
+            })
+            .collect::<Vec<_>>();
+
+        match serde_json::to_string_pretty(&sessions) {
+            Ok(json) => Ok(json),
+            Err(err) => Err(NeedleError::FailedToExportHistory(err.into())),
+        }
+    }
+
+    fn filter<F: Fn(&Session) -> bool>(&self, predicate: F) -> Self {
+        Self {
+            sessions: self
+                .sessions
+                .iter()
+                .filter(|session| predicate(session))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn export<L>(session: &Session, laps: L) -> ExportedSession<'_, L> {
+        ExportedSession {
+            mode: &session.mode,
+            label: session.label.as_deref().unwrap_or(""),
+            started_at: session.started_at.to_rfc3339(),
+            ended_at: session.ended_at.to_rfc3339(),
+            elapsed: Self::seconds(&session.elapsed),
+            paused: Self::seconds(&session.paused),
+            laps,
+        }
+    }
+
+    /// Seconds rounded to milliseconds
+    fn seconds(duration: &Duration) -> f64 {
+        duration.as_millis() as f64 / 1_000.0
+    }
+
+    fn history_file(create_dir: bool) -> NeedleErr<PathBuf> {
+        NeedleConfig::config_path(create_dir, Some(Self::HISTORY_FILE))
+    }
+}
+
+impl Session {
+    /// Wall-clock time between start and end, including pauses
+    pub fn wall_time(&self) -> TimeDelta {
+        self.ended_at - self.started_at
+    }
+}
+
+#[cfg(test)]
+fn session(label: &str, started_at: &str, minutes: u64) -> Session {
+    let started_at = DateTime::parse_from_rfc3339(started_at)
+        .unwrap()
+        .with_timezone(&Utc);
+    let elapsed = Duration::from_secs(minutes * 60);
+
+    Session {
+        mode: "CountUpTimer".to_string(),
+        label: Some(label.to_string()),
+        started_at,
+        ended_at: started_at + TimeDelta::from_std(elapsed).unwrap(),
+        elapsed,
+        paused: Duration::new(0, 0),
+        laps: vec![],
+    }
+}
+
+#[test]
+fn test_history_0001() {
+    use chrono::TimeZone;
+
+    let history = SessionHistory {
+        sessions: vec![
+            // Monday
+            session("build", "2025-01-06T09:00:00Z", 30),
+            session("review", "2025-01-06T23:30:00Z", 15),
+            session("build", "2025-01-12T10:00:00Z", 45),
+            session("build", "2025-01-13T10:00:00Z", 60),
+        ],
+    };
+    let monday = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+    let utc = Zone::parse("UTC").unwrap();
+    let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+
+    assert_eq!(2, history.on_day(monday, utc).sessions.len());
+    // 23:30 UTC is Tuesday in Tokyo
+    assert_eq!(1, history.on_day(monday, tokyo).sessions.len());
+    assert_eq!(3, history.in_week(monday, utc).sessions.len());
+    assert_eq!(
+        Duration::from_secs(75 * 60),
+        history.in_week(monday, utc).with_label("build").total()
+    );
+    let from = Utc.with_ymd_and_hms(2025, 1, 7, 0, 0, 0).unwrap();
+    let to = Utc.with_ymd_and_hms(2025, 1, 13, 10, 0, 0).unwrap();
+
+    // `to` is excluded
+    assert_eq!(1, history.between(&from, &to).sessions.len());
+    assert_eq!(vec!["build", "review"], history.labels());
+}
+
+#[test]
+fn test_history_0002() {
+    let mut first = session("build, \"fix\"", "2025-01-06T09:00:00Z", 30);
+
+    first.paused = Duration::from_millis(1_500);
+    first.laps = vec![
+        Lap {
+            split: Duration::from_secs(600),
+            total: Duration::from_secs(600),
+        },
+        Lap {
+            split: Duration::from_millis(300_250),
+            total: Duration::from_millis(900_250),
+        },
+    ];
+
+    let mut second = session("", "2025-01-06T10:00:00Z", 1);
+
+    second.label = None;
+
+    let path = std::env::temp_dir().join("needle_test_history_0002.toml");
+    let _ = fs::remove_file(&path);
+
+    SessionHistory::append_to(&path, &first).unwrap();
+    SessionHistory::append_to(&path, &second).unwrap();
+
+    let history = SessionHistory::load_from(&path).unwrap();
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(vec![first, second], history.sessions);
+    assert_eq!(
+        "mode,label,started_at,ended_at,elapsed,paused,laps\n\
+         CountUpTimer,\"build, \"\"fix\"\"\",2025-01-06T09:00:00+00:00,2025-01-06T09:30:00+00:00,1800.0,1.5,600;900.25\n\
+         CountUpTimer,,2025-01-06T10:00:00+00:00,2025-01-06T10:01:00+00:00,60.0,0.0,\n",
+        history.to_csv().unwrap()
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&history.to_json().unwrap()).unwrap();
+
+    assert_eq!("build, \"fix\"", json[0]["label"]);
+    assert_eq!(1.5, json[0]["paused"]);
+    assert_eq!(serde_json::json!([600.0, 900.25]), json[0]["laps"]);
+    assert_eq!("", json[1]["label"]);
+    assert!(SessionHistory::load_from(&path)
+        .unwrap()
+        .sessions
+        .is_empty())
+}
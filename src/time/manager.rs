@@ -0,0 +1,460 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{
+    ClockSource, OpMode, Session, SystemClock, Time, TimeFormat, TimeSnapshot, TimerEvent,
+};
+use crate::{NeedleConfig, NeedleErr, NeedleError};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Debug, Formatter},
+    fs::{self, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+type ClockFactory = Box<dyn Fn() -> Box<dyn ClockSource> + Send + Sync>;
+
+/// Named timers, e.g. "build", "tea" and "meeting", each with its own mode
+/// and format. Timers are kept in the order they were added.
+pub struct TimerManager {
+    timers: Vec<(String, Time)>,
+    new_clock: ClockFactory,
+}
+
+/// Serializable state of `TimerManager`, saved next to `config.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimerManagerSnapshot {
+    #[serde(default)]
+    pub timers: Vec<NamedTimeSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedTimeSnapshot {
+    pub name: String,
+    pub snapshot: TimeSnapshot,
+}
+
+impl TimerManager {
+    pub fn new() -> Self {
+        Self::with_clock(|| Box::new(SystemClock))
+    }
+
+    /// Timers added are driven by clocks made by `new_clock`
+    pub fn with_clock<F>(new_clock: F) -> Self
+    where
+        F: Fn() -> Box<dyn ClockSource> + Send + Sync + 'static,
+    {
+        Self {
+            timers: vec![],
+            new_clock: Box::new(new_clock),
+        }
+    }
+
+    pub fn add(&mut self, name: &str, mode: OpMode, format: TimeFormat) -> NeedleErr<&mut Time> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(NeedleError::InvalidTimerName("name is empty".into()));
+        }
+        if self.get(name).is_some() {
+            return Err(NeedleError::InvalidTimerName(
+                format!("\"{}\" already exists", name).into(),
+            ));
+        }
+
+        let mut time = Time::with_clock(format, (self.new_clock)());
+
+        time.set_mode(mode);
+        self.timers.push((name.to_string(), time));
+
+        Ok(&mut self.timers.last_mut().expect("timer has just been added").1)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Time> {
+        let index = self.index(name)?;
+
+        Some(self.timers.remove(index).1)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Time> {
+        let index = self.index(name)?;
+
+        Some(&self.timers[index].1)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Time> {
+        let index = self.index(name)?;
+
+        Some(&mut self.timers[index].1)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.timers.iter().map(|(name, _)| name.as_str())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Starts or resumes the timer, doing nothing if it's already running
+    pub fn start(&mut self, name: &str) -> NeedleErr<()> {
+        let time = self.timer(name)?;
+
+        if !time.is_running() {
+            time.toggle_timer();
+        }
+
+        Ok(())
+    }
+
+    /// Pauses the timer, doing nothing if it isn't running
+    pub fn stop(&mut self, name: &str) -> NeedleErr<()> {
+        let time = self.timer(name)?;
+
+        if time.is_running() {
+            time.toggle_timer();
+        }
+
+        Ok(())
+    }
+
+    pub fn reset(&mut self, name: &str) -> NeedleErr<()> {
+        self.timer(name).map(|time| time.reset())
+    }
+
+    /// Ends the current run of the timer and resets it, returning the run
+    /// labeled with the name of the timer. As with `Time::finish_session`,
+    /// it's up to the caller to append it with `SessionHistory::append`.
+    pub fn finish_session(&mut self, name: &str) -> NeedleErr<Option<Session>> {
+        let session = self.timer(name)?.finish_session();
+
+        Ok(session.map(|session| Session {
+            label: Some(name.to_string()),
+            ..session
+        }))
+    }
+
+    pub fn running(&self) -> Vec<&str> {
+        self.filter(|time| time.is_running())
+    }
+
+    /// Timers which finished as of the last `TimerManager::poll_events`
+    pub fn finished(&self) -> Vec<&str> {
+        self.filter(|time| time.is_finished())
+    }
+
+    /// Events of every timer raised since the last call, tagged with the
+    /// name of the timer
+    pub fn poll_events(&mut self) -> Vec<(String, TimerEvent)> {
+        self.timers
+            .iter_mut()
+            .flat_map(|(name, time)| {
+                time.poll_events()
+                    .into_iter()
+                    .map(|event| (name.clone(), event))
+            })
+            .collect()
+    }
+
+    pub fn snapshot(&self) -> TimerManagerSnapshot {
+        TimerManagerSnapshot {
+            timers: self
+                .timers
+                .iter()
+                .map(|(name, time)| NamedTimeSnapshot {
+                    name: name.clone(),
+                    snapshot: time.snapshot(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replaces every timer with the ones of `snapshot`
+    pub fn restore(&mut self, snapshot: &TimerManagerSnapshot) -> NeedleErr<()> {
+        let timers = std::mem::take(&mut self.timers);
+
+        for named in &snapshot.timers {
+            let time = self.add(
+                &named.name,
+                named.snapshot.mode.clone(),
+                named.snapshot.format.clone(),
+            );
+
+            match time {
+                Ok(time) => time.restore(&named.snapshot),
+                // Keeps the timers there were before
+                Err(err) => {
+                    self.timers = timers;
+
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+
+        self.timers.iter().position(|(timer, _)| timer == name)
+    }
+
+    fn timer(&mut self, name: &str) -> NeedleErr<&mut Time> {
+        self.get_mut(name)
+            .ok_or_else(|| NeedleError::TimerNotFound(name.into()))
+    }
+
+    fn filter<F: Fn(&Time) -> bool>(&self, predicate: F) -> Vec<&str> {
+        self.timers
+            .iter()
+            .filter(|(_, time)| predicate(time))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+impl Default for TimerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for TimerManager {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerManager")
+            .field("timers", &self.timers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TimerManagerSnapshot {
+    const SNAPSHOT_FILE: &'static str = "timers.toml";
+
+    pub fn save(&self) -> NeedleErr<()> {
+        self.save_to(&Self::snapshot_file(true)?)
+    }
+
+    /// Returns `None` if no snapshot has been saved
+    pub fn load() -> NeedleErr<Option<Self>> {
+        Self::load_from(&Self::snapshot_file(false)?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> NeedleErr<()> {
+        let snapshot = match toml::to_string(self) {
+            Ok(snapshot) => Ok(snapshot),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }?;
+        let file = match OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+        {
+            Ok(file) => Ok(file),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }?;
+        let mut buf_writer = BufWriter::new(file);
+
+        match write!(buf_writer, "{}", snapshot) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> NeedleErr<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let snapshot = match fs::read_to_string(path) {
+            Ok(snapshot) => Ok(snapshot),
+            Err(err) => Err(NeedleError::FailedToReadSnapshot(err.into())),
+        }?;
+
+        match toml::from_str(&snapshot) {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(err) => Err(NeedleError::FailedToReadSnapshot(err.into())),
+        }
+    }
+
+    fn snapshot_file(create_dir: bool) -> NeedleErr<PathBuf> {
+        NeedleConfig::config_path(create_dir, Some(Self::SNAPSHOT_FILE))
+    }
+}
+
+#[cfg(test)]
+fn mock_manager() -> (TimerManager, super::MockClock) {
+    use chrono::{TimeZone, Utc};
+
+    let clock = super::MockClock::new(Utc.with_ymd_and_hms(2025, 1, 1, 12, 34, 56).unwrap());
+    let shared = clock.clone();
+
+    (
+        TimerManager::with_clock(move || Box::new(shared.clone())),
+        clock,
+    )
+}
+
+#[test]
+fn test_manager_0001() {
+    use std::time::Duration;
+
+    let (mut timers, clock) = mock_manager();
+
+    timers
+        .add("build", OpMode::CountUpTimer, TimeFormat::HourMinSec)
+        .unwrap();
+    timers
+        .add(
+            "tea",
+            OpMode::CountDownTimer(Duration::from_secs(180)),
+            TimeFormat::Custom("%M:%S".to_string()),
+        )
+        .unwrap();
+    timers
+        .add("meeting", OpMode::Clock, TimeFormat::HourMinSec)
+        .unwrap();
+    assert_eq!(
+        vec!["build", "tea", "meeting"],
+        timers.names().collect::<Vec<_>>()
+    );
+
+    timers.start("build").unwrap();
+    timers.start("tea").unwrap();
+    // Starting twice doesn't pause
+    timers.start("tea").unwrap();
+    assert_eq!(vec!["build", "tea"], timers.running());
+
+    clock.advance(Duration::from_secs(180));
+    assert_eq!(
+        vec![
+            ("build".to_string(), TimerEvent::Started),
+            ("tea".to_string(), TimerEvent::Started),
+            ("tea".to_string(), TimerEvent::Finished),
+        ],
+        timers.poll_events()
+    );
+    assert_eq!(vec!["tea"], timers.finished());
+    assert_eq!("00:03:00", timers.get("build").unwrap().current_time());
+    assert_eq!("00:00", timers.get("tea").unwrap().current_time());
+
+    timers.stop("build").unwrap();
+
+    let session = timers.finish_session("build").unwrap().unwrap();
+
+    assert_eq!(Some("build".to_string()), session.label);
+    assert_eq!(Duration::from_secs(180), session.elapsed);
+    assert_eq!(None, timers.finish_session("build").unwrap());
+
+    timers.reset("tea").unwrap();
+    assert!(timers.running().is_empty());
+    assert!(timers.finished().is_empty());
+    assert_eq!("03:00", timers.get("tea").unwrap().current_time());
+
+    assert!(timers.remove("meeting").is_some());
+    assert_eq!(2, timers.len());
+    assert!(matches!(
+        timers.start("meeting"),
+        Err(NeedleError::TimerNotFound(_))
+    ));
+    assert!(matches!(
+        timers.add("tea", OpMode::CountUpTimer, TimeFormat::HourMinSec),
+        Err(NeedleError::InvalidTimerName(_))
+    ));
+    assert!(matches!(
+        timers.add(" ", OpMode::CountUpTimer, TimeFormat::HourMinSec),
+        Err(NeedleError::InvalidTimerName(_))
+    ))
+}
+
+#[test]
+fn test_manager_0002() {
+    use std::time::Duration;
+
+    let (mut timers, clock) = mock_manager();
+
+    timers
+        .add("build", OpMode::CountUpTimer, TimeFormat::HourMinSec)
+        .unwrap();
+    timers
+        .add("tea", OpMode::CountUpTimer, TimeFormat::HourMinSecMSec)
+        .unwrap();
+    timers.start("build").unwrap();
+    clock.advance(Duration::from_secs(90));
+
+    let path = std::env::temp_dir().join("needle_test_manager_0002.toml");
+
+    timers.snapshot().save_to(&path).unwrap();
+
+    let snapshot = TimerManagerSnapshot::load_from(&path).unwrap().unwrap();
+    let (mut restored, clock) = mock_manager();
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(timers.snapshot(), snapshot);
+
+    restored.restore(&snapshot).unwrap();
+    // Running timers keep counting after being restored
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(vec!["build", "tea"], restored.names().collect::<Vec<_>>());
+    assert_eq!(vec!["build"], restored.running());
+    assert_eq!("00:01:40", restored.get("build").unwrap().current_time());
+    assert_eq!("00:00:00.000", restored.get("tea").unwrap().current_time())
+}
+
+#[test]
+fn test_manager_0003() {
+    use std::time::Duration;
+
+    let (mut timers, clock) = mock_manager();
+
+    timers
+        .add(" build ", OpMode::CountUpTimer, TimeFormat::HourMinSec)
+        .unwrap();
+    timers
+        .add("tea", OpMode::CountUpTimer, TimeFormat::HourMinSec)
+        .unwrap();
+    assert!(timers.get(" build").is_some());
+    assert!(timers.get_mut("build ").is_some());
+    timers.start(" tea ").unwrap();
+
+    let mut snapshot = timers.snapshot();
+
+    snapshot.timers[1].name = "build".to_string();
+    assert!(matches!(
+        timers.restore(&snapshot),
+        Err(NeedleError::InvalidTimerName(_))
+    ));
+    // Left untouched, still driven by the injected clock
+    assert_eq!(vec!["build", "tea"], timers.names().collect::<Vec<_>>());
+    timers
+        .add("coffee", OpMode::CountUpTimer, TimeFormat::HourMinSec)
+        .unwrap();
+    timers.start("coffee").unwrap();
+    clock.advance(Duration::from_secs(5));
+    assert_eq!("00:00:05", timers.get("coffee").unwrap().current_time());
+    assert!(timers.remove(" tea ").is_some());
+    assert_eq!(vec!["build", "coffee"], timers.names().collect::<Vec<_>>())
+}
+
+#[test]
+fn test_manager_0004() {
+    let (mut timers, _) = mock_manager();
+
+    timers
+        .add("build", OpMode::CountUpTimer, TimeFormat::HourMinSec)
+        .unwrap();
+    timers.start("build").unwrap();
+    assert_eq!(vec!["build"], timers.running());
+
+    timers.get_mut("build").unwrap().set_mode(OpMode::Clock);
+    assert!(timers.running().is_empty());
+    assert!(!timers.get("build").unwrap().is_running())
+}
@@ -0,0 +1,2661 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+mod alarm;
+mod calendar;
+mod chess;
+mod clock;
+mod cron;
+mod discontinuity;
+mod duration;
+mod event;
+mod history;
+mod manager;
+mod pattern;
+mod pomodoro;
+mod representation;
+mod sequence;
+mod snapshot;
+mod solar;
+mod splits;
+mod timecode;
+mod words;
+mod zone;
+
+pub use alarm::*;
+pub use calendar::*;
+pub use chess::{ChessClock, ChessIncrement, ChessSide, ChessSnapshot, ChessStatus};
+pub use clock::*;
+pub use cron::*;
+pub use discontinuity::{Discontinuity, SuspendPolicy};
+pub(crate) use duration::{deserialize_duration, deserialize_minutes, duration_to_config_str};
+pub use duration::{parse_duration, DurationFormat};
+pub use event::*;
+pub use history::*;
+pub use manager::*;
+pub use pattern::*;
+pub use pomodoro::*;
+pub use representation::*;
+pub use sequence::*;
+pub use snapshot::*;
+pub use solar::*;
+pub use splits::*;
+pub use timecode::*;
+pub use words::*;
+pub use zone::*;
+
+use crate::NeedleErr;
+use chess::ChessState;
+use chrono::{DateTime, Local, TimeDelta, TimeZone, Timelike, Utc};
+use discontinuity::ClockWatch;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum TimeFormat {
+    HourMinSec,
+    HourMinSecMSec,
+    Custom(String),
+    /// SMPTE timecode at the given frame rate
+    Timecode(FrameRate),
+}
+
+impl TimeFormat {
+    const DEFAULT_PATTERN: &'static str = "%H:%M:%S";
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        match self {
+            Self::Custom(pattern) => TimePattern::parse(pattern).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum OpMode {
+    Clock,
+    CountDownTimer(#[serde(deserialize_with = "duration::deserialize_duration")] Duration),
+    CountUpTimer,
+    WorldClock,
+    CountDownTo(DateTime<Utc>),
+    Pomodoro(Pomodoro),
+    Sequence(Sequence),
+    ChessClock(ChessClock),
+    Splits(SplitRun),
+    /// Counts down to the next occurrence of a cron expression
+    Cron(CronSchedule),
+    /// Counts down to the next event of the calendar set by `Time::set_calendar`
+    Calendar,
+    /// Counts down to the next sunrise, sunset, twilight or solar noon
+    Solar(Coordinates),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lap {
+    pub split: Duration,
+    pub total: Duration,
+}
+
+#[derive(Debug)]
+pub struct Time {
+    format: TimeFormat,
+    pattern: Option<TimePattern>,
+    representation: Representation,
+    mode: OpMode,
+    zone: Zone,
+    world_clock: Vec<WorldClock>,
+    overtime: bool,
+    overtime_prefix: String,
+    duration_format: DurationFormat,
+    start_time: Instant,
+    stop_time: Option<Instant>,
+    /// Elapsed time carried over from a restored snapshot
+    offset: Duration,
+    started: bool,
+    /// Wall-clock time the current run was started at
+    run_started_at: Option<DateTime<Utc>>,
+    /// Time the current run spent paused, up to the last resume
+    paused: Duration,
+    laps: Vec<Lap>,
+    events: Vec<TimerEvent>,
+    finished: bool,
+    phase: Option<PomodoroPhase>,
+    segment: Option<usize>,
+    chess: Option<ChessState>,
+    alarms: AlarmScheduler,
+    /// Length of `Time::snooze_alarm`
+    snooze: Duration,
+    /// Next occurrence of `OpMode::Cron`, rolled over by `Time::poll_events`
+    cron_next: Option<DateTime<Utc>>,
+    calendar: Option<CalendarSource>,
+    /// Next event of `calendar`, rolled over by `Time::poll_events`
+    calendar_next: Option<UpcomingEvent>,
+    /// Next event of `OpMode::Solar`, rolled over by `Time::poll_events`
+    solar_next: Option<(SolarEvent, DateTime<Utc>)>,
+    suspend_policy: SuspendPolicy,
+    watch: ClockWatch,
+    clock: Box<dyn ClockSource>,
+}
+
+impl Time {
+    const MINUTE_SECS: u64 = 60;
+    const HOUR_SECS: u64 = Self::MINUTE_SECS * 60;
+    const DAY_SECS: u64 = Self::HOUR_SECS * 24;
+    const OVERTIME_PREFIX: &'static str = "-";
+
+    pub fn new(format: TimeFormat) -> Self {
+        Self::with_clock(format, Box::new(SystemClock))
+    }
+
+    pub fn with_clock(format: TimeFormat, clock: Box<dyn ClockSource>) -> Self {
+        Self {
+            pattern: Self::parse_pattern(&format),
+            format,
+            representation: Representation::Clock,
+            mode: OpMode::Clock,
+            zone: Zone::Local,
+            world_clock: vec![],
+            overtime: false,
+            overtime_prefix: Self::OVERTIME_PREFIX.to_string(),
+            duration_format: DurationFormat::default(),
+            start_time: clock.now(),
+            stop_time: None,
+            offset: Duration::new(0, 0),
+            started: false,
+            run_started_at: None,
+            paused: Duration::new(0, 0),
+            laps: vec![],
+            events: vec![],
+            finished: false,
+            phase: None,
+            segment: None,
+            chess: None,
+            alarms: AlarmScheduler::default(),
+            snooze: AlarmScheduler::DEFAULT_SNOOZE,
+            cron_next: None,
+            calendar: None,
+            calendar_next: None,
+            solar_next: None,
+            suspend_policy: SuspendPolicy::default(),
+            watch: ClockWatch::new(clock.as_ref(), Zone::Local),
+            clock,
+        }
+    }
+
+    #[inline]
+    pub fn mode(&self) -> OpMode {
+        self.mode.clone()
+    }
+
+    pub fn set_mode(&mut self, mode: OpMode) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.laps.clear();
+            self.finished = false;
+            self.run_started_at = None;
+            self.paused = Duration::new(0, 0);
+            // Modes without a timer must not be left running either
+            self.started = false;
+            self.start_time = self.clock.now();
+            self.stop_time = None;
+            self.offset = Duration::new(0, 0);
+
+            self.phase = self.pomodoro().map(|status| status.phase);
+            self.segment = self.sequence().map(|status| status.index);
+            self.chess = self.new_chess_state();
+            self.cron_next = self.new_cron_next();
+            self.solar_next = self.new_solar_next();
+        }
+    }
+
+    pub fn set_format(&mut self, format: TimeFormat) {
+        self.pattern = Self::parse_pattern(&format);
+        self.format = format;
+    }
+
+    #[inline]
+    pub fn representation(&self) -> Representation {
+        self.representation
+    }
+
+    /// Replaces the clock digits of `Clock` and `WorldClock` modes.
+    /// Timers keep using `TimeFormat`.
+    #[inline]
+    pub fn set_representation(&mut self, representation: Representation) {
+        self.representation = representation
+    }
+
+    #[inline]
+    pub fn zone(&self) -> Zone {
+        self.zone
+    }
+
+    pub fn set_zone(&mut self, zone: Zone) {
+        self.zone = zone;
+        self.alarms.set_zone(zone, &self.clock.utc_now());
+        self.solar_next = self.new_solar_next();
+        self.watch.reset_offset(zone);
+    }
+
+    #[inline]
+    pub fn alarms(&self) -> &AlarmScheduler {
+        &self.alarms
+    }
+
+    /// Schedules `alarms` in the timezone of the clock, replacing the
+    /// previous ones. `snooze` is the length of `Time::snooze_alarm`, e.g.
+    /// `AlarmConfig::snooze`.
+    pub fn set_alarms(&mut self, alarms: &[Alarm], snooze: Duration) {
+        self.alarms = AlarmScheduler::new(alarms, self.zone, &self.clock.utc_now());
+        self.snooze = snooze;
+    }
+
+    #[inline]
+    pub fn snooze(&self) -> Duration {
+        self.snooze
+    }
+
+    /// Calendar counted down to by `OpMode::Calendar`
+    pub fn set_calendar(&mut self, calendar: Option<CalendarSource>) {
+        self.calendar = calendar;
+        self.calendar_next = self.new_calendar_next();
+    }
+
+    /// Next event of the calendar set by `Time::set_calendar`
+    pub fn calendar_event(&self) -> Option<UpcomingEvent> {
+        let now = self.clock.utc_now();
+
+        // The event may have started without being polled yet
+        self.calendar_next
+            .clone()
+            .filter(|event| event.start > now)
+            .or_else(|| self.new_calendar_next())
+    }
+
+    #[inline]
+    pub fn suspend_policy(&self) -> SuspendPolicy {
+        self.suspend_policy
+    }
+
+    #[inline]
+    pub fn set_suspend_policy(&mut self, policy: SuspendPolicy) {
+        self.suspend_policy = policy
+    }
+
+    /// Next event of `OpMode::Solar` and when it happens
+    pub fn solar_event(&self) -> Option<(SolarEvent, DateTime<Utc>)> {
+        let now = self.clock.utc_now();
+
+        self.solar_next
+            .filter(|(_, time)| *time > now)
+            .or_else(|| self.new_solar_next())
+    }
+
+    /// Snoozes the alarm at `index` for the length set by `Time::set_alarms`
+    pub fn snooze_alarm(&mut self, index: usize) -> bool {
+        self.alarms
+            .snooze(index, self.snooze, &self.clock.utc_now())
+    }
+
+    #[inline]
+    pub fn dismiss_alarm(&mut self, index: usize) -> bool {
+        self.alarms.dismiss(index)
+    }
+
+    #[inline]
+    pub fn world_clock(&self) -> &[WorldClock] {
+        &self.world_clock
+    }
+
+    pub fn set_world_clock(&mut self, world_clock: &[WorldClock]) {
+        self.world_clock = world_clock.to_vec()
+    }
+
+    #[inline]
+    pub fn set_duration_format(&mut self, duration_format: DurationFormat) {
+        self.duration_format = duration_format
+    }
+
+    /// Keeps a `CountDownTimer` counting past zero, rendered with `prefix`.
+    /// Resuming a paused countdown in overtime continues the overtime
+    /// instead of restarting it.
+    pub fn set_overtime(&mut self, enable: bool, prefix: &str) {
+        self.overtime = enable;
+        self.overtime_prefix = prefix.to_string();
+    }
+
+    pub fn is_overtime(&self) -> bool {
+        match self.mode {
+            OpMode::CountDownTimer(duration) => self.overtime && self.elapsed() > duration,
+            _ => false,
+        }
+    }
+
+    pub fn toggle_timer(&mut self) {
+        // A completed run is kept until `finish_attempt` is called
+        if self.splits().is_some_and(|status| status.finished) {
+            return;
+        }
+
+        match self.mode {
+            OpMode::CountDownTimer(_) | OpMode::Sequence(_) => {
+                let duration = self.countdown().unwrap_or(Duration::new(0, 0));
+                let now = self.clock.now();
+
+                self.started = !self.started;
+
+                if self.started {
+                    self.start_time = match self.stop_time.take() {
+                        // Resume from where the timer has been stopped
+                        Some(time)
+                            if self.overtime
+                                || self.offset + (time - self.start_time) < duration =>
+                        {
+                            self.paused += now - time;
+                            self.events.push(TimerEvent::Resumed);
+
+                            self.start_time + (now - time)
+                        }
+                        // Has been previously stopped after reaching target duration
+                        _ => {
+                            self.offset = Duration::new(0, 0);
+                            self.finished = false;
+                            self.start_run();
+                            self.events.push(TimerEvent::Started);
+
+                            now
+                        }
+                    };
+                } else {
+                    self.stop_time = Some(now);
+                    self.events.push(TimerEvent::Paused)
+                }
+            }
+            OpMode::CountUpTimer | OpMode::Pomodoro(_) | OpMode::Splits(_) => {
+                let now = self.clock.now();
+
+                self.started = !self.started;
+
+                if self.started {
+                    self.start_time = match self.stop_time.take() {
+                        // Resume from where the timer has been stopped
+                        Some(time) => {
+                            self.paused += now - time;
+                            self.events.push(TimerEvent::Resumed);
+
+                            self.start_time + (now - time)
+                        }
+                        None => {
+                            self.start_run();
+                            self.events.push(TimerEvent::Started);
+
+                            now
+                        }
+                    };
+                } else {
+                    self.stop_time = Some(now);
+                    self.events.push(TimerEvent::Paused)
+                }
+            }
+            OpMode::ChessClock(ref clock) => {
+                let now = self.clock.now();
+
+                if let Some(ref mut chess) = self.chess {
+                    let first = chess.status(now).active.is_none();
+
+                    if let Some(side) = chess.toggle(clock, now) {
+                        if first {
+                            self.start_run();
+                            self.events.push(TimerEvent::Started);
+                        }
+                        self.events.push(TimerEvent::TurnChanged(side));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Whether the timer is counting, or a side of the chess clock is
+    pub fn is_running(&self) -> bool {
+        match self.mode {
+            OpMode::ChessClock(_) => self
+                .chess()
+                .is_some_and(|status| status.active.is_some() && status.flagged.is_none()),
+            _ => self.started,
+        }
+    }
+
+    /// Whether the countdown has reached zero as of the last
+    /// `Time::poll_events`, or the run of `OpMode::Splits` is complete
+    pub fn is_finished(&self) -> bool {
+        self.finished || self.splits().is_some_and(|status| status.finished)
+    }
+
+    /// Stops the timer and rewinds it to zero, keeping mode and format
+    pub fn reset(&mut self) {
+        self.started = false;
+        self.start_time = self.clock.now();
+        self.stop_time = None;
+        self.offset = Duration::new(0, 0);
+        self.run_started_at = None;
+        self.paused = Duration::new(0, 0);
+        self.laps.clear();
+        self.finished = false;
+        self.phase = self.pomodoro().map(|status| status.phase);
+        self.segment = self.sequence().map(|status| status.index);
+        self.chess = self.new_chess_state();
+    }
+
+    /// Current run as a history entry ending now. `None` if the timer hasn't
+    /// been started since the last reset.
+    pub fn session(&self) -> Option<Session> {
+        let started_at = self.run_started_at?;
+        let paused = match self.stop_time {
+            Some(time) if !self.started => self.paused + (self.clock.now() - time),
+            _ => self.paused,
+        };
+
+        Some(Session {
+            mode: self.mode.to_string(),
+            label: None,
+            started_at,
+            ended_at: self.clock.utc_now(),
+            elapsed: self.elapsed(),
+            paused,
+            laps: self.laps.clone(),
+        })
+    }
+
+    /// Ends the current run and resets the timer, returning the run to be
+    /// appended with `SessionHistory::append`. The run isn't recorded
+    /// otherwise, even when a countdown finishes.
+    pub fn finish_session(&mut self) -> Option<Session> {
+        let session = self.session();
+
+        self.reset();
+
+        session
+    }
+
+    /// Takes the events raised since the last call. Meant to be called once
+    /// per frame, as countdown completion and phase changes are only
+    /// detected here.
+    pub fn poll_events(&mut self) -> Vec<TimerEvent> {
+        for discontinuity in self.watch.check(self.clock.as_ref(), self.zone) {
+            self.handle_discontinuity(&discontinuity);
+            self.events.push(TimerEvent::ClockChanged(discontinuity));
+        }
+
+        let finished = match (&self.mode, self.countdown()) {
+            (_, Some(duration)) => {
+                (self.started || self.stop_time.is_some()) && self.elapsed() >= duration
+            }
+            (OpMode::CountDownTo(target), _) => self.clock.utc_now() >= *target,
+            (OpMode::ChessClock(_), _) => {
+                self.chess().is_some_and(|status| status.flagged.is_some())
+            }
+            _ => false,
+        };
+
+        if finished && !self.finished {
+            self.events.push(TimerEvent::Finished);
+        }
+        self.finished = finished;
+
+        // Fires on every occurrence, then counts down to the next one
+        if self
+            .cron_next
+            .is_some_and(|next| self.clock.utc_now() >= next)
+        {
+            self.events.push(TimerEvent::Occurrence);
+            self.cron_next = self.new_cron_next();
+        }
+
+        if self
+            .solar_next
+            .is_some_and(|(_, time)| self.clock.utc_now() >= time)
+        {
+            self.events.push(TimerEvent::Occurrence);
+            self.solar_next = self.new_solar_next();
+        }
+
+        if let Some(ref mut calendar) = self.calendar {
+            // Unreadable changes keep the events read before
+            if calendar.poll(self.clock.now()).unwrap_or(false) {
+                self.calendar_next = self.new_calendar_next();
+            }
+        }
+
+        if self
+            .calendar_next
+            .as_ref()
+            .is_some_and(|event| self.clock.utc_now() >= event.start)
+        {
+            if self.mode == OpMode::Calendar {
+                self.events.push(TimerEvent::EventStarted);
+            }
+            self.calendar_next = self.new_calendar_next();
+        }
+
+        if let Some(status) = self.pomodoro() {
+            if self.phase != Some(status.phase) {
+                self.events.push(TimerEvent::PhaseChanged(status.phase));
+            }
+            self.phase = Some(status.phase);
+        }
+
+        if let Some(status) = self.sequence() {
+            if self.segment != Some(status.index) {
+                self.events.push(TimerEvent::SegmentChanged(status.index));
+            }
+            self.segment = Some(status.index);
+        }
+
+        let alarms = self.alarms.poll(&self.clock.utc_now());
+
+        self.events
+            .extend(alarms.into_iter().map(TimerEvent::Alarm));
+
+        std::mem::take(&mut self.events)
+    }
+
+    /// Instant the string of `Time::current_time` changes next, or an alarm
+    /// is due. `None` while nothing changes, e.g. a paused timer.
+    ///
+    /// Events are only raised by `Time::poll_events`, so polling in between
+    /// is only needed to notice clock discontinuities early.
+    pub fn next_change(&self) -> Option<Instant> {
+        let now = self.clock.utc_now();
+        let until = |target: &DateTime<Utc>| (*target - now).to_std().ok();
+        let display = match self.mode {
+            OpMode::Clock => self.until_wall_change(&self.zone, &now),
+            OpMode::WorldClock if !self.world_clock.is_empty() => self
+                .world_clock
+                .iter()
+                .filter_map(|entry| self.until_wall_change(&entry.timezone, &now))
+                .min(),
+            OpMode::WorldClock => self.until_wall_change(&self.zone, &now),
+            OpMode::CountDownTimer(duration) if self.started => {
+                let elapsed = self.elapsed();
+
+                match duration.checked_sub(elapsed) {
+                    Some(remaining) if !remaining.is_zero() => self.until_decrease(&remaining),
+                    _ if self.overtime => self.until_increase(&elapsed.saturating_sub(duration)),
+                    _ => None,
+                }
+            }
+            OpMode::CountUpTimer if self.started => self.until_increase(&self.elapsed()),
+            OpMode::Pomodoro(pomodoro) if self.started => {
+                self.until_decrease(&pomodoro.status(self.elapsed()).remaining)
+            }
+            OpMode::Sequence(ref sequence) if self.started => sequence
+                .status(self.elapsed())
+                .and_then(|status| self.until_decrease(&status.remaining)),
+            OpMode::Splits(ref run) if self.started => {
+                let run_time = run.run_time(self.elapsed());
+
+                match run_time.to_std() {
+                    Ok(run_time) => self.until_increase(&run_time),
+                    Err(_) => self.until_decrease(&run_time.abs().to_std().unwrap_or_default()),
+                }
+            }
+            OpMode::ChessClock(_) => self.chess().and_then(|status| match status.active {
+                Some(ChessSide::White) => self.until_decrease(&status.white),
+                Some(ChessSide::Black) => self.until_decrease(&status.black),
+                None => None,
+            }),
+            OpMode::CountDownTo(target) => {
+                until(&target).and_then(|remaining| self.until_decrease(&remaining))
+            }
+            OpMode::Cron(_) => self
+                .cron_next
+                .and_then(|next| until(&next))
+                .and_then(|remaining| self.until_decrease(&remaining)),
+            OpMode::Calendar => self
+                .calendar_next
+                .as_ref()
+                .and_then(|event| until(&event.start))
+                .and_then(|remaining| self.until_decrease(&remaining))
+                // The file is checked for changes at this interval
+                .map(|remaining| remaining.min(CalendarSource::RELOAD_INTERVAL))
+                .or(self
+                    .calendar
+                    .as_ref()
+                    .map(|_| CalendarSource::RELOAD_INTERVAL)),
+            OpMode::Solar(_) => self
+                .solar_next
+                .and_then(|(_, time)| until(&time))
+                .and_then(|remaining| self.until_decrease(&remaining)),
+            _ => None,
+        };
+        let alarm = self
+            .alarms
+            .next_alarm()
+            .map(|(_, next)| until(&next).unwrap_or_default());
+
+        display
+            .into_iter()
+            .chain(alarm)
+            .min()
+            .map(|delay| self.clock.now() + delay)
+    }
+
+    /// Instant to render the next frame at, `Time::next_change` held back to
+    /// `frame_limit` frames per second since `last_frame`. A `frame_limit`
+    /// of 0 doesn't limit.
+    pub fn next_frame(&self, last_frame: Instant, frame_limit: u8) -> Option<Instant> {
+        let change = self.next_change()?;
+
+        match frame_limit {
+            0 => Some(change),
+            limit => Some(change.max(last_frame + Duration::from_secs(1) / limit as u32)),
+        }
+    }
+
+    pub fn snapshot(&self) -> TimeSnapshot {
+        TimeSnapshot {
+            mode: self.mode.clone(),
+            format: self.format.clone(),
+            elapsed: self.elapsed(),
+            running: self.started,
+            saved_at: self.clock.utc_now(),
+            laps: self.laps.clone(),
+            chess: self
+                .chess
+                .as_ref()
+                .map(|chess| chess.snapshot(self.clock.now())),
+        }
+    }
+
+    /// Restores a snapshot taken by `Time::snapshot`.
+    ///
+    /// A running timer keeps counting for the wall-clock time passed since
+    /// the snapshot was saved, as `Instant` doesn't survive restarts.
+    pub fn restore(&mut self, snapshot: &TimeSnapshot) {
+        let now = self.clock.now();
+        let downtime = (self.clock.utc_now() - snapshot.saved_at)
+            .to_std()
+            .unwrap_or(Duration::new(0, 0));
+
+        self.set_format(snapshot.format.clone());
+        self.mode = snapshot.mode.clone();
+        self.laps = snapshot.laps.clone();
+        self.started = snapshot.running;
+        // Pauses before the snapshot aren't known
+        self.run_started_at = (snapshot.running || !snapshot.elapsed.is_zero())
+            .then(|| snapshot.saved_at - TimeDelta::from_std(snapshot.elapsed).unwrap_or_default());
+        self.paused = Duration::new(0, 0);
+        self.start_time = now;
+        self.stop_time = if snapshot.running { None } else { Some(now) };
+        self.offset = if snapshot.running {
+            snapshot.elapsed + downtime
+        } else {
+            snapshot.elapsed
+        };
+        self.finished = false;
+        self.phase = self.pomodoro().map(|status| status.phase);
+        self.segment = self.sequence().map(|status| status.index);
+        // The side to move keeps counting like a running timer
+        self.chess = match (&self.mode, &snapshot.chess) {
+            (OpMode::ChessClock(_), Some(chess)) => Some(ChessState::restore(chess, downtime, now)),
+            _ => self.new_chess_state(),
+        };
+        self.cron_next = self.new_cron_next();
+        self.solar_next = self.new_solar_next();
+    }
+
+    pub fn chess(&self) -> Option<ChessStatus> {
+        self.chess
+            .as_ref()
+            .map(|chess| chess.status(self.clock.now()))
+    }
+
+    /// Remaining time of white and black
+    pub fn chess_to_str(&self) -> Option<[String; 2]> {
+        self.chess().map(|status| {
+            [
+                self.duration_to_str(&status.white),
+                self.duration_to_str(&status.black),
+            ]
+        })
+    }
+
+    pub fn splits(&self) -> Option<SplitStatus> {
+        match self.mode {
+            OpMode::Splits(ref run) => Some(run.status(&self.laps, self.elapsed())),
+            _ => None,
+        }
+    }
+
+    /// Split table with the time and delta against the personal best of
+    /// each segment. Segments faster than their best segment are marked
+    /// with `*`.
+    pub fn splits_to_str(&self) -> Vec<String> {
+        let OpMode::Splits(ref run) = self.mode else {
+            return vec![];
+        };
+        let splits = run.splits(&self.laps);
+        let width = splits
+            .iter()
+            .map(|split| split.name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        splits
+            .iter()
+            .map(|split| {
+                let time = match split.time {
+                    Some(time) => self.duration_to_str(&time),
+                    None => "-".to_string(),
+                };
+                let delta = match split.delta {
+                    Some(delta) => self.delta_to_str(&delta),
+                    None => String::new(),
+                };
+
+                format!(
+                    "{:width$}  {}  {}{}",
+                    split.name,
+                    time,
+                    delta,
+                    if split.gold { " *" } else { "" },
+                    width = width
+                )
+                .trim_end()
+                .to_string()
+            })
+            .collect()
+    }
+
+    /// Ends the current attempt of `OpMode::Splits`, updating the personal
+    /// best and best segments of the run and resetting the timer for the
+    /// next attempt. Returns whether the attempt is a new personal best.
+    ///
+    /// Write the run back out with `SplitRun::save` to keep the result.
+    pub fn finish_attempt(&mut self) -> Option<bool> {
+        let OpMode::Splits(ref mut run) = self.mode else {
+            return None;
+        };
+        let personal_best = run.record_attempt(&self.laps);
+
+        self.laps.clear();
+        self.started = false;
+        self.start_time = self.clock.now();
+        self.stop_time = None;
+        self.offset = Duration::new(0, 0);
+        self.run_started_at = None;
+        self.paused = Duration::new(0, 0);
+        self.finished = false;
+
+        Some(personal_best)
+    }
+
+    pub fn sequence(&self) -> Option<SequenceStatus> {
+        match self.mode {
+            OpMode::Sequence(ref sequence) => sequence.status(self.elapsed()),
+            _ => None,
+        }
+    }
+
+    pub fn pomodoro(&self) -> Option<PomodoroStatus> {
+        match self.mode {
+            OpMode::Pomodoro(pomodoro) => Some(pomodoro.status(self.elapsed())),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn laps(&self) -> &[Lap] {
+        &self.laps
+    }
+
+    pub fn record_lap(&mut self) -> Option<Lap> {
+        if self.splits().is_some_and(|status| status.finished) {
+            return None;
+        }
+
+        match self.mode {
+            OpMode::CountUpTimer | OpMode::Splits(_) if self.started => {
+                let total = self.elapsed();
+                let split = match self.laps.last() {
+                    Some(lap) => total.saturating_sub(lap.total),
+                    None => total,
+                };
+                let lap = Lap { split, total };
+
+                self.laps.push(lap);
+                self.events.push(TimerEvent::LapRecorded(lap));
+
+                // Last split of the run stops the timer
+                if self.splits().is_some_and(|status| status.finished) {
+                    self.started = false;
+                    self.stop_time = Some(self.clock.now());
+                    self.events.push(TimerEvent::RunCompleted);
+                }
+
+                Some(lap)
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn clear_laps(&mut self) {
+        self.laps.clear()
+    }
+
+    pub fn laps_to_str(&self) -> Vec<String> {
+        let digit = (self.laps.len().to_string().len() as u32).max(2);
+
+        self.laps
+            .iter()
+            .enumerate()
+            .map(|(i, lap)| {
+                format!(
+                    "Lap {}  {}  {}",
+                    Self::format_to_digit(digit, i as u32 + 1),
+                    self.duration_to_str(&lap.split),
+                    self.duration_to_str(&lap.total)
+                )
+            })
+            .collect()
+    }
+
+    pub fn current_time(&self) -> String {
+        match self.mode {
+            OpMode::CountDownTimer(duration) => {
+                let delta = self.elapsed();
+
+                if self.overtime && delta > duration {
+                    return self.overtime_to_str(&(delta - duration));
+                }
+
+                let delta = if delta > duration {
+                    Duration::new(0, 0)
+                } else {
+                    duration - delta
+                };
+
+                self.duration_to_str(&delta)
+            }
+            OpMode::CountUpTimer => self.duration_to_str(&self.elapsed()),
+            OpMode::Clock => self.zoned_time_to_str(&self.zone, &self.clock.utc_now()),
+            OpMode::WorldClock => {
+                let now = self.clock.utc_now();
+
+                if self.world_clock.is_empty() {
+                    return self.zoned_time_to_str(&self.zone, &now);
+                }
+
+                let width = self
+                    .world_clock
+                    .iter()
+                    .map(|entry| entry.label.chars().count())
+                    .max()
+                    .unwrap_or(0);
+
+                self.world_clock
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "{:width$}  {}",
+                            entry.label,
+                            self.zoned_time_to_str(&entry.timezone, &now),
+                            width = width
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            OpMode::CountDownTo(target) => self.time_until_to_str(&target),
+            OpMode::Cron(ref schedule) => {
+                let now = self.clock.utc_now();
+
+                // The occurrence may have passed without being polled yet
+                match self
+                    .cron_next
+                    .filter(|next| *next > now)
+                    .or_else(|| schedule.next_after(&now))
+                {
+                    Some(next) => self.time_until_to_str(&next),
+                    None => self.duration_to_str(&Duration::new(0, 0)),
+                }
+            }
+            OpMode::Solar(_) => match self.solar_event() {
+                Some((event, time)) => {
+                    format!("{}\n{}", self.time_until_to_str(&time), event)
+                }
+                None => self.duration_to_str(&Duration::new(0, 0)),
+            },
+            // Title of the event on the second line
+            OpMode::Calendar => match self.calendar_event() {
+                Some(event) => format!(
+                    "{}\n{}",
+                    self.time_until_to_str(&event.start),
+                    event.summary
+                ),
+                None => self.duration_to_str(&Duration::new(0, 0)),
+            },
+            OpMode::Pomodoro(pomodoro) => {
+                self.duration_to_str(&pomodoro.status(self.elapsed()).remaining)
+            }
+            OpMode::Sequence(ref sequence) => {
+                let remaining = sequence
+                    .status(self.elapsed())
+                    .map(|status| status.remaining)
+                    .unwrap_or(Duration::new(0, 0));
+
+                self.duration_to_str(&remaining)
+            }
+            OpMode::Splits(ref run) => {
+                let run_time = run.run_time(self.elapsed());
+
+                // Start delay counts up from below zero
+                match run_time.to_std() {
+                    Ok(run_time) => self.duration_to_str(&run_time),
+                    Err(_) => self.delta_to_str(&run_time),
+                }
+            }
+            OpMode::ChessClock(_) => match self.chess() {
+                Some(ChessStatus {
+                    active: Some(ChessSide::Black),
+                    black,
+                    ..
+                }) => self.duration_to_str(&black),
+                Some(status) => self.duration_to_str(&status.white),
+                None => self.duration_to_str(&Duration::new(0, 0)),
+            },
+        }
+    }
+
+    fn new_cron_next(&self) -> Option<DateTime<Utc>> {
+        match self.mode {
+            OpMode::Cron(ref schedule) => schedule.next_after(&self.clock.utc_now()),
+            _ => None,
+        }
+    }
+
+    fn handle_discontinuity(&mut self, discontinuity: &Discontinuity) {
+        match discontinuity {
+            // Pomodoro and sequence phases follow `elapsed`
+            Discontinuity::Suspended(duration) if self.suspend_policy == SuspendPolicy::Include => {
+                if self.started {
+                    self.offset += *duration;
+                }
+                if let Some(ref mut chess) = self.chess {
+                    chess.suspend(*duration);
+                }
+            }
+            // Occurrences computed from the later time may be too far ahead
+            Discontinuity::WallClockJump(delta) if *delta < TimeDelta::zero() => {
+                self.alarms.set_zone(self.zone, &self.clock.utc_now());
+                self.cron_next = self.new_cron_next();
+                self.solar_next = self.new_solar_next();
+                self.calendar_next = self.new_calendar_next();
+            }
+            _ => (),
+        }
+    }
+
+    fn new_solar_next(&self) -> Option<(SolarEvent, DateTime<Utc>)> {
+        match self.mode {
+            OpMode::Solar(coordinates) => coordinates.next_event(self.zone, &self.clock.utc_now()),
+            _ => None,
+        }
+    }
+
+    fn new_calendar_next(&self) -> Option<UpcomingEvent> {
+        self.calendar
+            .as_ref()
+            .and_then(|calendar| calendar.calendar().next_event(&self.clock.utc_now()))
+    }
+
+    fn new_chess_state(&self) -> Option<ChessState> {
+        match self.mode {
+            OpMode::ChessClock(ref clock) => Some(ChessState::new(clock, self.clock.now())),
+            _ => None,
+        }
+    }
+
+    /// Bounds of the unit displayed for `value` by timers, e.g. the second
+    /// it falls in for `TimeFormat::HourMinSec`
+    fn unit_bounds(&self, value: &Duration) -> Option<(Duration, Duration)> {
+        let resolution = match self.format {
+            TimeFormat::HourMinSec => Duration::from_secs(1),
+            TimeFormat::HourMinSecMSec => Duration::from_millis(1),
+            TimeFormat::Custom(_) => self.pattern.as_ref()?.resolution()?,
+            TimeFormat::Timecode(rate) => {
+                let frames = rate.frames(value);
+
+                return Some((rate.frame_time(frames), rate.frame_time(frames + 1)));
+            }
+        };
+
+        Some(Self::tick_bounds(value, &resolution))
+    }
+
+    fn tick_bounds(value: &Duration, resolution: &Duration) -> (Duration, Duration) {
+        let resolution = resolution.as_nanos().max(1);
+        let start = value.as_nanos() / resolution * resolution;
+        let nanos = |nanos: u128| Duration::from_nanos(nanos.min(u64::MAX as u128) as u64);
+
+        (nanos(start), nanos(start + resolution))
+    }
+
+    /// Time until a value counting up from `value` is displayed differently
+    fn until_increase(&self, value: &Duration) -> Option<Duration> {
+        self.unit_bounds(value).map(|(_, end)| end - *value)
+    }
+
+    /// Time until a value counting down from `value` is displayed differently
+    fn until_decrease(&self, value: &Duration) -> Option<Duration> {
+        if value.is_zero() {
+            return None;
+        }
+
+        self.unit_bounds(value).map(|(start, _)| *value - start)
+    }
+
+    /// Time until the clock of `zone` is displayed differently
+    fn until_wall_change(&self, zone: &Zone, now: &DateTime<Utc>) -> Option<Duration> {
+        let local = zone.to_local(now);
+        let since_midnight =
+            Duration::new(local.num_seconds_from_midnight() as u64, local.nanosecond());
+
+        match self.representation.resolution() {
+            Some(resolution) if self.representation.is_epoch_aligned() => {
+                let since_epoch = (*now - DateTime::UNIX_EPOCH).to_std().ok()?;
+                let (_, end) = Self::tick_bounds(&since_epoch, &resolution);
+
+                Some(end - since_epoch)
+            }
+            Some(resolution) => {
+                let (_, end) = Self::tick_bounds(&since_midnight, &resolution);
+
+                Some(end - since_midnight)
+            }
+            None => self.until_increase(&since_midnight),
+        }
+    }
+
+    fn time_until_to_str(&self, target: &DateTime<Utc>) -> String {
+        // Anchored to wall-clock time rather than `Instant` so the
+        // target is kept across restarts
+        let delta = (*target - self.clock.utc_now())
+            .to_std()
+            .unwrap_or(Duration::new(0, 0));
+
+        // Always show days as the target may be far ahead
+        self.format_duration(
+            &delta,
+            DurationFormat {
+                days: true,
+                ..self.duration_format
+            },
+        )
+    }
+
+    /// Total length of countdown modes driven by `Instant`
+    fn countdown(&self) -> Option<Duration> {
+        match self.mode {
+            OpMode::CountDownTimer(duration) => Some(duration),
+            OpMode::Sequence(ref sequence) => Some(sequence.duration()),
+            _ => None,
+        }
+    }
+
+    fn start_run(&mut self) {
+        self.run_started_at = Some(self.clock.utc_now());
+        self.paused = Duration::new(0, 0);
+    }
+
+    fn elapsed(&self) -> Duration {
+        if self.started {
+            self.offset + (self.clock.now() - self.start_time)
+        } else if let Some(time) = self.stop_time {
+            self.offset + (time - self.start_time)
+        } else {
+            // Timer hasn't been started
+            self.offset
+        }
+    }
+
+    fn zoned_time_to_str(&self, zone: &Zone, time: &DateTime<Utc>) -> String {
+        match zone {
+            Zone::Local => self.time_to_str(&time.with_timezone(&Local)),
+            Zone::Named(tz) => self.time_to_str(&time.with_timezone(tz)),
+            Zone::Fixed(offset) => self.time_to_str(&time.with_timezone(offset)),
+        }
+    }
+
+    fn time_to_str<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        if let Some(time) = self.representation.format(time) {
+            return time;
+        }
+
+        match self.format {
+            TimeFormat::HourMinSec => {
+                let hour = Self::format_to_digit(2, time.hour());
+                let minute = Self::format_to_digit(2, time.minute());
+                let second = Self::format_to_digit(2, time.second());
+
+                format!("{}:{}:{}", hour, minute, second)
+            }
+            TimeFormat::HourMinSecMSec => {
+                let hour = Self::format_to_digit(2, time.hour());
+                let minute = Self::format_to_digit(2, time.minute());
+                let second = Self::format_to_digit(2, time.second());
+                let millisecond = Self::format_to_digit(3, time.nanosecond() / 1_000_000);
+
+                format!("{}:{}:{}.{}", hour, minute, second, millisecond)
+            }
+            TimeFormat::Custom(ref pattern) => match self.pattern {
+                Some(ref parsed) => parsed.format_time(time),
+                None => pattern.clone(),
+            },
+            TimeFormat::Timecode(rate) => {
+                // Leap seconds are reported as nanoseconds past 1s
+                let since_midnight = Duration::new(
+                    time.num_seconds_from_midnight() as u64,
+                    time.nanosecond().min(999_999_999),
+                );
+
+                Timecode::time_of_day(&since_midnight, rate).to_string()
+            }
+        }
+    }
+
+    #[inline]
+    fn duration_to_str(&self, delta: &Duration) -> String {
+        self.format_duration(delta, self.duration_format)
+    }
+
+    fn format_duration(&self, delta: &Duration, layout: DurationFormat) -> String {
+        let secs = delta.as_secs();
+        let days = if layout.days {
+            secs / Self::DAY_SECS
+        } else {
+            0
+        };
+        let prefix = if days > 0 {
+            format!("{}d ", days)
+        } else {
+            String::new()
+        };
+        let hour = ((secs - days * Self::DAY_SECS) / Self::HOUR_SECS) as u32;
+        let minute = ((secs / Self::MINUTE_SECS) % 60) as u32;
+        let second = (secs % Self::MINUTE_SECS) as u32;
+        let millisecond = match self.format {
+            TimeFormat::HourMinSecMSec => format!(
+                ".{}",
+                Self::format_to_digit(3, (delta.as_millis() % 1000) as u32)
+            ),
+            _ => String::new(),
+        };
+
+        match self.format {
+            TimeFormat::HourMinSec | TimeFormat::HourMinSecMSec => {
+                let minute_second = format!(
+                    "{}:{}{}",
+                    Self::format_to_digit(2, minute),
+                    Self::format_to_digit(2, second),
+                    millisecond
+                );
+
+                if days == 0 && layout.trim {
+                    if hour == 0 {
+                        format!(
+                            "{}:{}{}",
+                            minute,
+                            Self::format_to_digit(2, second),
+                            millisecond
+                        )
+                    } else {
+                        format!("{}:{}", hour, minute_second)
+                    }
+                } else {
+                    format!(
+                        "{}{}:{}",
+                        prefix,
+                        Self::format_to_digit(2, hour),
+                        minute_second
+                    )
+                }
+            }
+            TimeFormat::Custom(ref pattern) => match self.pattern {
+                Some(ref parsed) if parsed.has_days() => parsed.format_duration(delta),
+                Some(ref parsed) => format!(
+                    "{}{}",
+                    prefix,
+                    parsed.format_duration(&(*delta - Duration::from_secs(days * Self::DAY_SECS)))
+                ),
+                None => pattern.clone(),
+            },
+            TimeFormat::Timecode(rate) => format!(
+                "{}{}",
+                prefix,
+                Timecode::from_duration(
+                    &(*delta - Duration::from_secs(days * Self::DAY_SECS)),
+                    rate
+                )
+            ),
+        }
+    }
+
+    /// Signed duration, with `+` only when `delta` is positive
+    fn delta_to_str(&self, delta: &TimeDelta) -> String {
+        let sign = match delta.cmp(&TimeDelta::zero()) {
+            std::cmp::Ordering::Less => "-",
+            std::cmp::Ordering::Equal => "",
+            std::cmp::Ordering::Greater => "+",
+        };
+
+        format!(
+            "{}{}",
+            sign,
+            self.duration_to_str(&delta.abs().to_std().unwrap_or(Duration::new(0, 0)))
+        )
+    }
+
+    fn overtime_to_str(&self, delta: &Duration) -> String {
+        match self.pattern {
+            Some(ref pattern) => pattern.format_overtime(delta, &self.overtime_prefix),
+            None => format!("{}{}", self.overtime_prefix, self.duration_to_str(delta)),
+        }
+    }
+
+    fn parse_pattern(format: &TimeFormat) -> Option<TimePattern> {
+        match format {
+            // Invalid patterns are rejected on config load, fall back to plain text otherwise
+            TimeFormat::Custom(pattern) => TimePattern::parse(pattern).ok(),
+            _ => None,
+        }
+    }
+
+    fn format_to_digit(digit: u32, value: u32) -> String {
+        if digit <= 1 {
+            return value.to_string();
+        }
+
+        let mut prefix = String::new();
+
+        for i in 1..digit {
+            if value < 10u32.pow(i) {
+                prefix += "0";
+            }
+        }
+
+        format!("{}{}", prefix, value)
+    }
+}
+
+macro_rules! time_format_impl_from {
+    ($type:ty) => {
+        impl From<TimeFormat> for $type {
+            fn from(element: TimeFormat) -> Self {
+                match element {
+                    TimeFormat::HourMinSec => 0,
+                    TimeFormat::HourMinSecMSec => 1,
+                    TimeFormat::Custom(_) => 2,
+                    TimeFormat::Timecode(_) => 3,
+                }
+            }
+        }
+
+        impl From<$type> for TimeFormat {
+            fn from(val: $type) -> Self {
+                match val {
+                    1 => TimeFormat::HourMinSecMSec,
+                    2 => TimeFormat::Custom(TimeFormat::DEFAULT_PATTERN.to_string()),
+                    3 => TimeFormat::Timecode(FrameRate::Fps30),
+                    _ => TimeFormat::HourMinSec,
+                }
+            }
+        }
+    };
+}
+
+time_format_impl_from! { i8 }
+time_format_impl_from! { u8 }
+time_format_impl_from! { i16 }
+time_format_impl_from! { u16 }
+time_format_impl_from! { i32 }
+time_format_impl_from! { u32 }
+
+impl Display for TimeFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            TimeFormat::HourMinSec => "HourMinSec",
+            TimeFormat::HourMinSecMSec => "HourMinSecMSec",
+            TimeFormat::Custom(_) => "Custom",
+            TimeFormat::Timecode(_) => "Timecode",
+        };
+
+        write!(f, "{}", format)
+    }
+}
+
+macro_rules! op_mode_impl_from {
+    ($type:ty) => {
+        impl From<OpMode> for $type {
+            fn from(element: OpMode) -> Self {
+                match element {
+                    OpMode::Clock => 0,
+                    OpMode::CountUpTimer => 1,
+                    OpMode::CountDownTimer(_) => 2,
+                    OpMode::WorldClock => 3,
+                    OpMode::CountDownTo(_) => 4,
+                    OpMode::Pomodoro(_) => 5,
+                    OpMode::Sequence(_) => 6,
+                    OpMode::ChessClock(_) => 7,
+                    OpMode::Splits(_) => 8,
+                    OpMode::Cron(_) => 9,
+                    OpMode::Calendar => 10,
+                    OpMode::Solar(_) => 11,
+                }
+            }
+        }
+
+        impl From<$type> for OpMode {
+            fn from(val: $type) -> Self {
+                match val {
+                    1 => OpMode::CountUpTimer,
+                    2 => OpMode::CountDownTimer(Duration::new(0, 0)),
+                    3 => OpMode::WorldClock,
+                    4 => OpMode::CountDownTo(DateTime::UNIX_EPOCH),
+                    5 => OpMode::Pomodoro(Pomodoro::default()),
+                    6 => OpMode::Sequence(Sequence {
+                        name: String::new(),
+                        steps: vec![],
+                    }),
+                    7 => OpMode::ChessClock(ChessClock::default()),
+                    8 => OpMode::Splits(SplitRun {
+                        game: String::new(),
+                        category: String::new(),
+                        offset: TimeDelta::zero(),
+                        attempts: 0,
+                        segments: vec![],
+                    }),
+                    9 => OpMode::Cron(CronSchedule::default()),
+                    10 => OpMode::Calendar,
+                    11 => OpMode::Solar(Coordinates::default()),
+                    _ => OpMode::Clock,
+                }
+            }
+        }
+    };
+}
+
+op_mode_impl_from! { i8 }
+op_mode_impl_from! { u8 }
+op_mode_impl_from! { i16 }
+op_mode_impl_from! { u16 }
+op_mode_impl_from! { i32 }
+op_mode_impl_from! { u32 }
+
+impl Display for OpMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            OpMode::Clock => "Clock",
+            OpMode::CountDownTimer(_) => "CountDownTimer",
+            OpMode::CountUpTimer => "CountUpTimer",
+            OpMode::WorldClock => "WorldClock",
+            OpMode::CountDownTo(_) => "CountDownTo",
+            OpMode::Pomodoro(_) => "Pomodoro",
+            OpMode::Sequence(_) => "Sequence",
+            OpMode::ChessClock(_) => "ChessClock",
+            OpMode::Splits(_) => "Splits",
+            OpMode::Cron(_) => "Cron",
+            OpMode::Calendar => "Calendar",
+            OpMode::Solar(_) => "Solar",
+        };
+
+        write!(f, "{}", format)
+    }
+}
+
+/// Parses an RFC 3339 time, shared by the tests of the submodules
+#[cfg(test)]
+fn utc(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[cfg(test)]
+fn mock_time(format: TimeFormat) -> (Time, MockClock) {
+    use chrono::TimeZone;
+
+    let clock = MockClock::new(
+        chrono::Utc
+            .with_ymd_and_hms(2025, 1, 1, 12, 34, 56)
+            .unwrap(),
+    );
+
+    (Time::with_clock(format, Box::new(clock.clone())), clock)
+}
+
+#[test]
+fn test_time_0001() {
+    let (time, clock) = mock_time(TimeFormat::HourMinSec);
+    let expected = clock
+        .utc_now()
+        .with_timezone(&Local)
+        .format("%H:%M:%S")
+        .to_string();
+
+    assert_eq!(OpMode::Clock, time.mode());
+    assert_eq!(expected, time.current_time())
+}
+
+#[test]
+fn test_time_0002() {
+    let (time, clock) = mock_time(TimeFormat::HourMinSecMSec);
+
+    clock.advance(Duration::from_millis(1_250));
+
+    let expected = clock
+        .utc_now()
+        .with_timezone(&Local)
+        .format("%H:%M:%S%.3f")
+        .to_string();
+
+    assert_eq!(expected, time.current_time())
+}
+
+#[test]
+fn test_time_0003() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    // Toggling has no effect in clock mode
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(5));
+
+    let expected = clock
+        .utc_now()
+        .with_timezone(&Local)
+        .format("%H:%M:%S")
+        .to_string();
+
+    assert_eq!(expected, time.current_time())
+}
+
+#[test]
+fn test_time_0004() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    clock.advance(Duration::from_secs(3));
+    assert_eq!("00:00:00", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(5));
+    assert_eq!("00:00:05", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(10));
+    assert_eq!("00:00:05", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(3));
+    assert_eq!("00:00:08", time.current_time())
+}
+
+#[test]
+fn test_time_0005() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSecMSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_millis(2_345));
+
+    assert_eq!("00:00:02.345", time.current_time())
+}
+
+#[test]
+fn test_time_0006() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(10)));
+    assert_eq!("00:00:10", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(4));
+    assert_eq!("00:00:06", time.current_time());
+
+    // Paused time must not be counted
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(100));
+    assert_eq!("00:00:06", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(2));
+    assert_eq!("00:00:04", time.current_time());
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!("00:00:00", time.current_time())
+}
+
+#[test]
+fn test_time_0007() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(10)));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(15));
+    time.toggle_timer();
+    assert_eq!("00:00:00", time.current_time());
+
+    // Starting a finished countdown restarts it
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(1));
+    assert_eq!("00:00:09", time.current_time())
+}
+
+#[test]
+fn test_time_0008() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(7));
+
+    // Setting the same mode keeps the running timer
+    time.set_mode(OpMode::CountUpTimer);
+    assert_eq!("00:00:07", time.current_time());
+
+    // Switching mode resets the timer
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(30)));
+    clock.advance(Duration::from_secs(7));
+    assert_eq!("00:00:30", time.current_time());
+
+    time.set_mode(OpMode::CountUpTimer);
+    clock.advance(Duration::from_secs(7));
+    assert_eq!("00:00:00", time.current_time())
+}
+
+#[test]
+fn test_time_0009() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    assert_eq!(None, time.record_lap());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(5));
+    time.record_lap();
+    clock.advance(Duration::from_secs(7));
+    time.record_lap();
+
+    assert_eq!(
+        &[
+            Lap {
+                split: Duration::from_secs(5),
+                total: Duration::from_secs(5)
+            },
+            Lap {
+                split: Duration::from_secs(7),
+                total: Duration::from_secs(12)
+            }
+        ],
+        time.laps()
+    );
+    assert_eq!(
+        vec![
+            "Lap 01  00:00:05  00:00:05".to_string(),
+            "Lap 02  00:00:07  00:00:12".to_string()
+        ],
+        time.laps_to_str()
+    );
+
+    time.clear_laps();
+    assert!(time.laps().is_empty())
+}
+
+#[test]
+fn test_time_0010() {
+    let (mut time, _) = mock_time(TimeFormat::HourMinSec);
+
+    assert_eq!(None, time.record_lap());
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(10)));
+    time.toggle_timer();
+    assert_eq!(None, time.record_lap())
+}
+
+#[test]
+fn test_time_0011() {
+    let (mut time, clock) = mock_time(TimeFormat::Custom("%-I:%M %p".to_string()));
+    let expected = clock
+        .utc_now()
+        .with_timezone(&Local)
+        .format("%-I:%M %p")
+        .to_string();
+
+    assert_eq!(expected, time.current_time());
+
+    time.set_format(TimeFormat::Custom("%M:%S.%2f".to_string()));
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_millis(3_723_450));
+    assert_eq!("62:03.45", time.current_time())
+}
+
+#[test]
+fn test_time_0012() {
+    assert!(TimeFormat::HourMinSec.validate().is_ok());
+    assert!(TimeFormat::Custom("%H:%M".to_string()).validate().is_ok());
+    assert!(TimeFormat::Custom("%H:%K".to_string()).validate().is_err())
+}
+
+#[test]
+fn test_time_0013() {
+    let (mut time, _) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_zone(Zone::parse("Asia/Tokyo").unwrap());
+    assert_eq!("21:34:56", time.current_time());
+
+    time.set_zone(Zone::parse("-05:30").unwrap());
+    assert_eq!("07:04:56", time.current_time());
+
+    // Standard/daylight saving time is resolved from the bundled tz database
+    time.set_zone(Zone::parse("America/New_York").unwrap());
+    assert_eq!("07:34:56", time.current_time())
+}
+
+#[test]
+fn test_time_0014() {
+    let (mut time, _) = mock_time(TimeFormat::Custom("%H:%M".to_string()));
+
+    time.set_zone(Zone::parse("UTC").unwrap());
+    time.set_mode(OpMode::WorldClock);
+    assert_eq!("12:34", time.current_time());
+
+    time.set_world_clock(&[
+        WorldClock {
+            label: "Tokyo".to_string(),
+            timezone: Zone::parse("Asia/Tokyo").unwrap(),
+        },
+        WorldClock {
+            label: "London".to_string(),
+            timezone: Zone::parse("Europe/London").unwrap(),
+        },
+        WorldClock {
+            label: "SF".to_string(),
+            timezone: Zone::parse("America/Los_Angeles").unwrap(),
+        },
+    ]);
+    assert_eq!(
+        "Tokyo   21:34\nLondon  12:34\nSF      04:34",
+        time.current_time()
+    )
+}
+
+#[test]
+fn test_time_0015() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let target = Zone::parse("Asia/Tokyo")
+        .unwrap()
+        .parse_datetime("2025-01-03T22:00:00")
+        .unwrap();
+
+    time.set_mode(OpMode::CountDownTo(target));
+    assert_eq!("2d 00:25:04", time.current_time());
+
+    // Toggling doesn't pause a wall-clock countdown
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(2 * 86_400));
+    assert_eq!("00:25:04", time.current_time());
+
+    time.set_format(TimeFormat::Custom("%D:%H:%M".to_string()));
+    clock.set_wall(target - chrono::TimeDelta::days(3));
+    assert_eq!("3:00:00", time.current_time());
+
+    clock.set_wall(target + chrono::TimeDelta::seconds(1));
+    assert_eq!("0:00:00", time.current_time())
+}
+
+#[test]
+fn test_time_0016() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    assert_eq!(None, time.pomodoro());
+
+    time.set_mode(OpMode::Pomodoro(Pomodoro::default()));
+    assert_eq!("00:25:00", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(25 * 60 + 30));
+    assert_eq!("00:04:30", time.current_time());
+    assert_eq!(
+        Some(PomodoroStatus {
+            phase: PomodoroPhase::ShortBreak,
+            remaining: Duration::from_secs(4 * 60 + 30),
+            completed: 1,
+        }),
+        time.pomodoro()
+    );
+
+    // Paused time isn't counted
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(60 * 60));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(89 * 60 + 30));
+
+    let status = time.pomodoro().unwrap();
+
+    assert_eq!(PomodoroPhase::LongBreak, status.phase);
+    assert_eq!(4, status.completed);
+    assert_eq!("00:15:00", time.current_time())
+}
+
+#[test]
+fn test_time_0017() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(10)));
+    assert!(time.poll_events().is_empty());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(4));
+    time.toggle_timer();
+    time.toggle_timer();
+    assert_eq!(
+        vec![TimerEvent::Started, TimerEvent::Paused, TimerEvent::Resumed],
+        time.poll_events()
+    );
+
+    clock.advance(Duration::from_secs(6));
+    assert_eq!(vec![TimerEvent::Finished], time.poll_events());
+    // Reported only once
+    clock.advance(Duration::from_secs(1));
+    assert!(time.poll_events().is_empty());
+
+    // Restarting a finished countdown allows it to finish again
+    time.toggle_timer();
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(
+        vec![
+            TimerEvent::Paused,
+            TimerEvent::Started,
+            TimerEvent::Finished
+        ],
+        time.poll_events()
+    )
+}
+
+#[test]
+fn test_time_0018() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(3));
+
+    let lap = time.record_lap().unwrap();
+
+    assert_eq!(
+        vec![TimerEvent::Started, TimerEvent::LapRecorded(lap)],
+        time.poll_events()
+    );
+
+    time.set_mode(OpMode::Pomodoro(Pomodoro::default()));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(25 * 60));
+    assert_eq!(
+        vec![
+            TimerEvent::Started,
+            TimerEvent::PhaseChanged(PomodoroPhase::ShortBreak)
+        ],
+        time.poll_events()
+    );
+
+    clock.advance(Duration::from_secs(5 * 60));
+    assert_eq!(
+        vec![TimerEvent::PhaseChanged(PomodoroPhase::Work)],
+        time.poll_events()
+    )
+}
+
+#[test]
+fn test_time_0019() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let target = clock.utc_now() + chrono::TimeDelta::seconds(30);
+
+    time.set_mode(OpMode::CountDownTo(target));
+    assert!(time.poll_events().is_empty());
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(vec![TimerEvent::Finished], time.poll_events())
+}
+
+#[test]
+fn test_time_0020() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSecMSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_millis(5_500));
+    time.record_lap();
+    clock.advance(Duration::from_secs(2));
+
+    let snapshot = time.snapshot();
+    let (mut restored, restored_clock) = mock_time(TimeFormat::HourMinSec);
+
+    // Restarted 10 seconds later
+    restored_clock.set_wall(clock.utc_now() + chrono::TimeDelta::seconds(10));
+    restored.restore(&snapshot);
+
+    assert_eq!(OpMode::CountUpTimer, restored.mode());
+    assert_eq!(time.laps(), restored.laps());
+    assert_eq!("00:00:17.500", restored.current_time());
+
+    restored_clock.advance(Duration::from_secs(1));
+    assert_eq!("00:00:18.500", restored.current_time());
+
+    restored.toggle_timer();
+    restored_clock.advance(Duration::from_secs(5));
+    restored.toggle_timer();
+    restored_clock.advance(Duration::from_secs(1));
+    assert_eq!("00:00:19.500", restored.current_time())
+}
+
+#[test]
+fn test_time_0021() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(60)));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(20));
+    time.toggle_timer();
+
+    let snapshot = time.snapshot();
+    let (mut restored, restored_clock) = mock_time(TimeFormat::HourMinSec);
+
+    // Paused timers don't advance while the app is closed
+    restored_clock.advance(Duration::from_secs(3_600));
+    restored.restore(&snapshot);
+    assert_eq!("00:00:40", restored.current_time());
+
+    restored.toggle_timer();
+    restored_clock.advance(Duration::from_secs(30));
+    assert_eq!("00:00:10", restored.current_time());
+    assert_eq!(vec![TimerEvent::Resumed], restored.poll_events());
+
+    restored_clock.advance(Duration::from_secs(10));
+    assert_eq!(vec![TimerEvent::Finished], restored.poll_events())
+}
+
+#[test]
+fn test_time_0022() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let sequence = Sequence {
+        name: "Intervals".to_string(),
+        steps: vec![
+            Step::Repeat {
+                repeat: 2,
+                steps: vec![
+                    Step::Segment(Segment {
+                        label: "Work".to_string(),
+                        length: Duration::from_secs(20),
+                        color: None,
+                    }),
+                    Step::Segment(Segment {
+                        label: "Rest".to_string(),
+                        length: Duration::from_secs(10),
+                        color: None,
+                    }),
+                ],
+            },
+            Step::Segment(Segment {
+                label: "Cooldown".to_string(),
+                length: Duration::from_secs(60),
+                color: None,
+            }),
+        ],
+    };
+
+    time.set_mode(OpMode::Sequence(sequence));
+    assert_eq!("00:00:20", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(25));
+    assert_eq!("00:00:05", time.current_time());
+    assert_eq!("Rest", time.sequence().unwrap().label);
+    assert_eq!(
+        vec![TimerEvent::Started, TimerEvent::SegmentChanged(1)],
+        time.poll_events()
+    );
+
+    clock.advance(Duration::from_secs(95));
+    assert_eq!("00:00:00", time.current_time());
+    assert_eq!(
+        vec![TimerEvent::Finished, TimerEvent::SegmentChanged(4)],
+        time.poll_events()
+    );
+
+    // Restarts once finished
+    time.toggle_timer();
+    time.toggle_timer();
+    assert_eq!("00:00:20", time.current_time())
+}
+
+#[test]
+fn test_time_0023() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_overtime(true, "-");
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(10)));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(10));
+    assert_eq!("00:00:00", time.current_time());
+    assert!(!time.is_overtime());
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!("-00:00:05", time.current_time());
+    assert!(time.is_overtime());
+    assert_eq!(
+        vec![TimerEvent::Started, TimerEvent::Finished],
+        time.poll_events()
+    );
+
+    // Pausing in overtime resumes the overtime
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(60));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(1));
+    assert_eq!("-00:00:06", time.current_time());
+
+    time.set_format(TimeFormat::Custom("%M:%S (%Oover)".to_string()));
+    time.set_overtime(true, "+");
+    assert_eq!("00:06 (+over)", time.current_time());
+
+    time.set_overtime(false, "-");
+    assert_eq!("00:00 (over)", time.current_time());
+    assert!(!time.is_overtime())
+}
+
+#[test]
+fn test_time_0024() {
+    let parse = |mode: &str| toml::from_str::<toml::Table>(mode).map(|table| table["mode"].clone());
+    let mode = |mode: &str| parse(mode).unwrap().try_into::<OpMode>().unwrap();
+
+    assert_eq!(
+        OpMode::CountDownTimer(Duration::from_secs(5_400)),
+        mode("mode = { CountDownTimer = \"1h30m\" }")
+    );
+    assert_eq!(
+        OpMode::CountDownTimer(Duration::from_secs(1_500)),
+        mode("mode = { CountDownTimer = \"PT25M\" }")
+    );
+    assert_eq!(
+        OpMode::CountDownTimer(Duration::from_secs(90)),
+        mode("mode = { CountDownTimer = 90 }")
+    );
+    assert_eq!(
+        OpMode::CountDownTimer(Duration::new(1, 500)),
+        mode("mode = { CountDownTimer = { secs = 1, nanos = 500 } }")
+    );
+
+    let err = parse("mode = { CountDownTimer = \"25 minuets\" }")
+        .unwrap()
+        .try_into::<OpMode>()
+        .unwrap_err();
+
+    assert!(err.to_string().contains("unknown unit \"minuets\" at 3"))
+}
+
+#[test]
+fn test_time_0025() {
+    let (mut time, _) = mock_time(TimeFormat::HourMinSec);
+    let layout = |days, trim| DurationFormat { days, trim };
+    let table = [
+        // (seconds, millis, days, trim, HourMinSec, HourMinSecMSec)
+        (0, 0, false, false, "00:00:00", "00:00:00.000"),
+        (0, 0, false, true, "0:00", "0:00.000"),
+        (0, 0, true, false, "00:00:00", "00:00:00.000"),
+        (0, 0, true, true, "0:00", "0:00.000"),
+        (59, 999, false, false, "00:00:59", "00:00:59.999"),
+        (59, 999, false, true, "0:59", "0:59.999"),
+        (60, 0, false, false, "00:01:00", "00:01:00.000"),
+        (303, 0, false, false, "00:05:03", "00:05:03.000"),
+        (303, 0, false, true, "5:03", "5:03.000"),
+        (303, 0, true, true, "5:03", "5:03.000"),
+        (3_599, 0, false, false, "00:59:59", "00:59:59.000"),
+        (3_599, 0, false, true, "59:59", "59:59.000"),
+        (3_600, 0, false, false, "01:00:00", "01:00:00.000"),
+        (3_600, 0, false, true, "1:00:00", "1:00:00.000"),
+        (3_905, 0, false, false, "01:05:05", "01:05:05.000"),
+        (3_905, 0, false, true, "1:05:05", "1:05:05.000"),
+        (3_905, 0, true, false, "01:05:05", "01:05:05.000"),
+        (36_000, 0, false, true, "10:00:00", "10:00:00.000"),
+        (86_399, 0, false, false, "23:59:59", "23:59:59.000"),
+        (86_399, 0, true, false, "23:59:59", "23:59:59.000"),
+        (86_400, 0, false, false, "24:00:00", "24:00:00.000"),
+        (86_400, 0, true, false, "1d 00:00:00", "1d 00:00:00.000"),
+        (86_400, 0, true, true, "1d 00:00:00", "1d 00:00:00.000"),
+        (93_784, 5, false, false, "26:03:04", "26:03:04.005"),
+        (93_784, 5, true, false, "1d 02:03:04", "1d 02:03:04.005"),
+        (93_784, 5, true, true, "1d 02:03:04", "1d 02:03:04.005"),
+        (359_999, 0, false, false, "99:59:59", "99:59:59.000"),
+        (360_000, 0, false, false, "100:00:00", "100:00:00.000"),
+        (360_000, 0, true, false, "4d 04:00:00", "4d 04:00:00.000"),
+        (3_600_000, 0, false, true, "1000:00:00", "1000:00:00.000"),
+        (
+            3_600_000,
+            0,
+            true,
+            false,
+            "41d 16:00:00",
+            "41d 16:00:00.000",
+        ),
+    ];
+
+    for (secs, millis, days, trim, hms, hms_msec) in table {
+        let delta = Duration::from_secs(secs) + Duration::from_millis(millis);
+
+        time.set_duration_format(layout(days, trim));
+        time.set_format(TimeFormat::HourMinSec);
+        assert_eq!(
+            hms,
+            time.duration_to_str(&delta),
+            "{} {} {}",
+            secs,
+            days,
+            trim
+        );
+        time.set_format(TimeFormat::HourMinSecMSec);
+        assert_eq!(
+            hms_msec,
+            time.duration_to_str(&delta),
+            "{} {} {}",
+            secs,
+            days,
+            trim
+        )
+    }
+}
+
+#[test]
+fn test_time_0026() {
+    let (mut time, clock) = mock_time(TimeFormat::Custom("%H:%M".to_string()));
+    let delta = Duration::from_secs(93_784);
+
+    assert_eq!("26:03", time.duration_to_str(&delta));
+
+    time.set_duration_format(DurationFormat {
+        days: true,
+        trim: false,
+    });
+    assert_eq!("1d 02:03", time.duration_to_str(&delta));
+
+    // Patterns with days handle the rollover themselves
+    time.set_format(TimeFormat::Custom("%D.%H:%M".to_string()));
+    assert_eq!("1.02:03", time.duration_to_str(&delta));
+
+    time.set_format(TimeFormat::HourMinSec);
+    time.set_duration_format(DurationFormat::default());
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(3_905));
+    assert_eq!("01:05:05", time.current_time())
+}
+
+#[test]
+fn test_time_0027() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    assert_eq!(None, time.chess());
+
+    time.set_mode(OpMode::ChessClock(ChessClock {
+        time: Duration::from_secs(180),
+        increment: ChessIncrement::Fischer(Duration::from_secs(2)),
+    }));
+    assert_eq!(
+        Some(["00:03:00".to_string(), "00:03:00".to_string()]),
+        time.chess_to_str()
+    );
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(30));
+    assert_eq!("00:02:30", time.current_time());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(10));
+    assert_eq!("00:02:50", time.current_time());
+    assert_eq!(
+        Some(["00:02:32".to_string(), "00:02:50".to_string()]),
+        time.chess_to_str()
+    );
+    assert_eq!(
+        vec![
+            TimerEvent::Started,
+            TimerEvent::TurnChanged(ChessSide::White),
+            TimerEvent::TurnChanged(ChessSide::Black)
+        ],
+        time.poll_events()
+    );
+
+    clock.advance(Duration::from_secs(170));
+
+    let status = time.chess().unwrap();
+
+    assert_eq!(Some(ChessSide::Black), status.flagged);
+    assert_eq!((1, 0), (status.white_moves, status.black_moves));
+    assert_eq!(vec![TimerEvent::Finished], time.poll_events());
+
+    // Flag fall ends the game
+    time.toggle_timer();
+    assert!(time.poll_events().is_empty())
+}
+
+#[test]
+fn test_time_0028() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let run = SplitRun {
+        game: "Celeste".to_string(),
+        category: "Any%".to_string(),
+        offset: TimeDelta::seconds(-2),
+        attempts: 0,
+        segments: ["Prologue", "Forsaken City"]
+            .iter()
+            .map(|name| SplitSegment {
+                name: name.to_string(),
+                personal_best: None,
+                best_segment: None,
+            })
+            .collect(),
+    };
+
+    time.set_mode(OpMode::Splits(run));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(1));
+    assert_eq!("-00:00:01", time.current_time());
+
+    clock.advance(Duration::from_secs(11));
+    time.record_lap();
+    clock.advance(Duration::from_secs(20));
+    time.record_lap();
+
+    let status = time.splits().unwrap();
+
+    assert!(status.finished);
+    assert_eq!(Duration::from_secs(20), status.segment_time);
+    assert_eq!(
+        TimerEvent::RunCompleted,
+        *time.poll_events().last().unwrap()
+    );
+    assert_eq!(None, time.record_lap());
+
+    // Stopped on the last split
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(5));
+    assert_eq!("00:00:30", time.current_time());
+    assert_eq!(Some(true), time.finish_attempt());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(13));
+    time.record_lap();
+
+    assert_eq!(
+        vec![
+            "Prologue       00:00:11  +00:00:01".to_string(),
+            "Forsaken City  00:00:30".to_string(),
+        ],
+        time.splits_to_str()
+    );
+    clock.advance(Duration::from_secs(8));
+    assert_eq!(Some(TimeDelta::seconds(-11)), time.splits().unwrap().delta);
+    assert_eq!(Some(false), time.finish_attempt());
+
+    let OpMode::Splits(run) = time.mode() else {
+        panic!("mode has been changed")
+    };
+
+    assert_eq!(2, run.attempts);
+    assert_eq!(Some(Duration::from_secs(30)), run.segments[1].personal_best);
+    assert_eq!(Some(Duration::from_secs(20)), run.segments[1].best_segment)
+}
+
+#[test]
+fn test_time_0029() {
+    let (mut time, clock) = mock_time(TimeFormat::Timecode(FrameRate::Fps25));
+    let expected = format!(
+        "{}:00",
+        clock.utc_now().with_timezone(&Local).format("%H:%M:%S")
+    );
+
+    assert_eq!(expected, time.current_time());
+
+    time.set_zone(Zone::parse("UTC").unwrap());
+    clock.advance(Duration::from_millis(520));
+    assert_eq!("12:34:56:13", time.current_time());
+
+    time.set_format(TimeFormat::Timecode(FrameRate::Fps29_97DropFrame));
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(60));
+    // 1798.2 frames
+    assert_eq!("00:00:59;28", time.current_time());
+    // Frame 1800 starts at 60.06s and is labelled past the dropped ;00 and ;01
+    clock.advance(Duration::from_millis(70));
+    assert_eq!("00:01:00;02", time.current_time());
+
+    time.set_duration_format(DurationFormat {
+        days: true,
+        trim: true,
+    });
+    clock.advance(Duration::from_secs(Time::DAY_SECS));
+    assert_eq!("1d 00:01:00;02", time.current_time())
+}
+
+#[test]
+fn test_time_0030() {
+    let (mut time, _) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_zone(Zone::parse("Asia/Tokyo").unwrap());
+    time.set_representation(Representation::Iso8601);
+    assert_eq!("2025-01-01T21:34:56+09:00", time.current_time());
+
+    time.set_representation(Representation::UnixSeconds);
+    time.set_mode(OpMode::WorldClock);
+    time.set_world_clock(&[WorldClock {
+        label: "UTC".to_string(),
+        timezone: Zone::parse("UTC").unwrap(),
+    }]);
+    assert_eq!("UTC  1735734896", time.current_time());
+
+    // Timers are unaffected
+    time.set_mode(OpMode::CountUpTimer);
+    assert_eq!("00:00:00", time.current_time())
+}
+
+#[test]
+fn test_time_0031() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let alarm = Alarm {
+        label: "Stand up".to_string(),
+        time: "12:40".parse().unwrap(),
+        repeat: Recurrence::Daily,
+        enabled: true,
+    };
+
+    time.set_zone(Zone::parse("UTC").unwrap());
+    time.set_alarms(&[alarm], Duration::from_secs(60));
+    clock.advance(Duration::from_secs(5 * 60));
+    assert!(time.poll_events().is_empty());
+
+    clock.advance(Duration::from_secs(4));
+    assert_eq!(
+        vec![TimerEvent::Alarm(AlarmEvent::Fired(0))],
+        time.poll_events()
+    );
+    assert_eq!(Duration::from_secs(60), time.snooze());
+    assert!(time.snooze_alarm(0));
+    clock.advance(Duration::from_secs(60));
+    assert_eq!(
+        vec![
+            TimerEvent::Alarm(AlarmEvent::Snoozed(0)),
+            TimerEvent::Alarm(AlarmEvent::Fired(0)),
+        ],
+        time.poll_events()
+    );
+    assert!(time.dismiss_alarm(0));
+
+    // Rescheduled to 12:40 in Tokyo, 03:40 UTC of the next day
+    time.set_zone(Zone::parse("Asia/Tokyo").unwrap());
+    assert_eq!(
+        Some((0, clock.utc_now() + TimeDelta::seconds(15 * 3_600 - 60))),
+        time.alarms().next_alarm()
+    )
+}
+
+#[test]
+fn test_time_0032() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let schedule = CronSchedule::parse("*/15 * * * *", Zone::parse("UTC").unwrap()).unwrap();
+
+    time.set_mode(OpMode::Cron(schedule));
+    assert_eq!("Cron", time.mode().to_string());
+    assert_eq!("00:10:04", time.current_time());
+
+    clock.advance(Duration::from_secs(10 * 60));
+    assert!(time.poll_events().is_empty());
+    assert_eq!("00:00:04", time.current_time());
+
+    // Rolls over to the next occurrence once fired
+    clock.advance(Duration::from_secs(4));
+    assert_eq!(vec![TimerEvent::Occurrence], time.poll_events());
+    assert_eq!("00:15:00", time.current_time());
+    assert!(time.poll_events().is_empty());
+
+    // Missed occurrences fire once
+    clock.advance(Duration::from_secs(3_600));
+    assert_eq!(vec![TimerEvent::Occurrence], time.poll_events());
+    assert_eq!("00:15:00", time.current_time());
+
+    time.set_mode(OpMode::Cron(
+        CronSchedule::parse("0 9 * * 1", Zone::parse("UTC").unwrap()).unwrap(),
+    ));
+    // 2025-01-01 is a Wednesday, next Monday 09:00 is 2025-01-06
+    assert_eq!("4d 19:15:00", time.current_time())
+}
+
+#[test]
+fn test_time_0033() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let path = std::env::temp_dir().join("needle_test_time_0033.ics");
+
+    std::fs::write(
+        &path,
+        "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:a\r\nSUMMARY:Lunch\r\n\
+         DTSTART:20250101T130000Z\r\nRRULE:FREQ=DAILY\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+    )
+    .unwrap();
+
+    time.set_mode(OpMode::Calendar);
+    assert_eq!("00:00:00", time.current_time());
+
+    time.set_calendar(Some(CalendarSource::new(&path, Zone::Local).unwrap()));
+    assert_eq!("00:25:04\nLunch", time.current_time());
+
+    clock.advance(Duration::from_secs(25 * 60 + 4));
+    assert_eq!(vec![TimerEvent::EventStarted], time.poll_events());
+    assert_eq!("1d 00:00:00\nLunch", time.current_time());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_time_0034() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let tokyo = Coordinates {
+        latitude: 35.6812,
+        longitude: 139.7671,
+    };
+
+    time.set_zone(Zone::parse("Asia/Tokyo").unwrap());
+    time.set_mode(OpMode::Solar(tokyo));
+
+    // 21:34:56 in Tokyo, astronomical dawn is at 05:19:40
+    let (event, dawn) = time.solar_event().unwrap();
+
+    assert_eq!(SolarEvent::AstronomicalDawn, event);
+    assert_eq!(
+        format!(
+            "{}\nAstronomical dawn",
+            time.duration_to_str(&(dawn - clock.utc_now()).to_std().unwrap())
+        ),
+        time.current_time()
+    );
+
+    clock.advance((dawn - clock.utc_now()).to_std().unwrap());
+    assert_eq!(vec![TimerEvent::Occurrence], time.poll_events());
+    assert_eq!(
+        Some(SolarEvent::NauticalDawn),
+        time.solar_event().map(|(event, _)| event)
+    );
+    assert!(time.current_time().ends_with("\nNautical dawn"))
+}
+
+#[test]
+fn test_time_0035() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(60));
+    clock.suspend(Duration::from_secs(600));
+    assert_eq!(
+        vec![
+            TimerEvent::Started,
+            TimerEvent::ClockChanged(Discontinuity::Suspended(Duration::from_secs(600))),
+        ],
+        time.poll_events()
+    );
+    assert_eq!("00:01:00", time.current_time());
+
+    time.set_suspend_policy(SuspendPolicy::Include);
+    clock.suspend(Duration::from_secs(600));
+    time.poll_events();
+    assert_eq!("00:11:00", time.current_time());
+
+    // Schedules are recomputed when the clock is set back
+    let schedule = CronSchedule::parse("0 * * * *", Zone::parse("UTC").unwrap()).unwrap();
+
+    time.set_mode(OpMode::Cron(schedule));
+    assert_eq!("00:04:04", time.current_time());
+    clock.set_wall(clock.utc_now() - TimeDelta::hours(2));
+    assert_eq!(
+        vec![TimerEvent::ClockChanged(Discontinuity::WallClockJump(
+            -TimeDelta::hours(2)
+        ))],
+        time.poll_events()
+    );
+    assert_eq!("00:04:04", time.current_time());
+    clock.advance(Duration::from_secs(4 * 60 + 4));
+    assert_eq!(vec![TimerEvent::Occurrence], time.poll_events())
+}
+
+#[test]
+fn test_time_0036() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+    let after = |time: &Time| {
+        time.next_change()
+            .map(|next| next.duration_since(clock.now()))
+    };
+
+    assert_eq!(Some(Duration::from_secs(1)), after(&time));
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(Some(Duration::from_millis(750)), after(&time));
+
+    time.set_format(TimeFormat::HourMinSecMSec);
+    assert_eq!(Some(Duration::from_millis(1)), after(&time));
+    time.set_format(TimeFormat::Custom("%H:%M".to_string()));
+    assert_eq!(Some(Duration::from_millis(3_750)), after(&time));
+    time.set_format(TimeFormat::Custom("Now".to_string()));
+    assert_eq!(None, after(&time));
+
+    // Julian days change every 864ms counted from the Unix epoch
+    time.set_format(TimeFormat::HourMinSec);
+    time.set_representation(Representation::JulianDay);
+    assert_eq!(Some(Duration::from_millis(678)), after(&time));
+    time.set_representation(Representation::Clock);
+
+    time.set_mode(OpMode::CountDownTimer(Duration::from_secs(10)));
+    assert_eq!(None, after(&time));
+    time.toggle_timer();
+    clock.advance(Duration::from_millis(2_400));
+    // 7.6s remaining is shown as 7s until 7s
+    assert_eq!(Some(Duration::from_millis(600)), after(&time));
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(None, after(&time));
+
+    time.set_format(TimeFormat::Timecode(FrameRate::Fps25));
+    time.set_mode(OpMode::CountUpTimer);
+    time.toggle_timer();
+    clock.advance(Duration::from_millis(50));
+    assert_eq!(Some(Duration::from_millis(30)), after(&time));
+    time.toggle_timer();
+    assert_eq!(None, after(&time));
+
+    // Alarms are due even while nothing is displayed differently
+    time.set_zone(Zone::parse("UTC").unwrap());
+    time.set_alarms(
+        &[Alarm {
+            label: "Soon".to_string(),
+            time: "12:35:10".parse().unwrap(),
+            repeat: Recurrence::Daily,
+            enabled: true,
+        }],
+        AlarmScheduler::DEFAULT_SNOOZE,
+    );
+    assert_eq!(Some(Duration::from_millis(1_300)), after(&time))
+}
+
+#[test]
+fn test_time_0037() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSecMSec);
+    let now = clock.now();
+
+    // Held back to 50 frames per second
+    assert_eq!(
+        Some(now + Duration::from_millis(20)),
+        time.next_frame(now, 50)
+    );
+    assert_eq!(
+        Some(now + Duration::from_millis(1)),
+        time.next_frame(now, 0)
+    );
+
+    time.set_format(TimeFormat::HourMinSec);
+    assert_eq!(Some(now + Duration::from_secs(1)), time.next_frame(now, 50));
+
+    time.set_mode(OpMode::CountUpTimer);
+    assert_eq!(None, time.next_frame(now, 50))
+}
+
+#[test]
+fn test_time_0038() {
+    use chrono::TimeZone;
+
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::CountUpTimer);
+    assert_eq!(None, time.session());
+
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(600));
+    time.record_lap();
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(120));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(300));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(30));
+
+    let session = time.finish_session().unwrap();
+
+    assert_eq!("CountUpTimer", session.mode);
+    assert_eq!(None, session.label);
+    assert_eq!(
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 34, 56).unwrap(),
+        session.started_at
+    );
+    assert_eq!(
+        Utc.with_ymd_and_hms(2025, 1, 1, 12, 52, 26).unwrap(),
+        session.ended_at
+    );
+    assert_eq!(Duration::from_secs(900), session.elapsed);
+    // Still paused when finished
+    assert_eq!(Duration::from_secs(150), session.paused);
+    assert_eq!(1, session.laps.len());
+    assert_eq!(None, time.session());
+    assert_eq!("00:00:00", time.current_time());
+
+    // A run isn't carried over to another mode
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(60));
+    time.toggle_timer();
+    time.set_mode(OpMode::Pomodoro(Pomodoro::default()));
+    assert_eq!(None, time.session())
+}
+
+#[test]
+fn test_time_0039() {
+    use chrono::TimeZone;
+
+    let (mut time, clock) = mock_time(TimeFormat::Timecode(FrameRate::Fps29_97DropFrame));
+
+    time.set_zone(Zone::parse("UTC").unwrap());
+    clock.set_wall(
+        Utc.with_ymd_and_hms(2025, 1, 1, 23, 59, 59).unwrap() + TimeDelta::milliseconds(950),
+    );
+    assert_eq!("23:59:59;29", time.current_time());
+
+    clock.set_wall(Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap());
+    assert_eq!("00:00:00;00", time.current_time())
+}
+
+#[test]
+fn test_time_0040() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_suspend_policy(SuspendPolicy::Include);
+    time.set_mode(OpMode::Pomodoro(Pomodoro {
+        work: Duration::from_secs(60),
+        short_break: Duration::from_secs(30),
+        long_break: Duration::from_secs(120),
+        cycles: 2,
+    }));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(50));
+    clock.suspend(Duration::from_secs(20));
+    time.poll_events();
+    assert_eq!(
+        Some(PomodoroPhase::ShortBreak),
+        time.pomodoro().map(|status| status.phase)
+    );
+
+    time.set_mode(OpMode::ChessClock(ChessClock {
+        time: Duration::from_secs(180),
+        increment: ChessIncrement::None,
+    }));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(30));
+    clock.suspend(Duration::from_secs(60));
+    time.poll_events();
+    assert_eq!("00:01:30", time.current_time());
+
+    // Excluded time leaves the running side alone
+    time.set_suspend_policy(SuspendPolicy::Exclude);
+    clock.suspend(Duration::from_secs(60));
+    time.poll_events();
+    assert_eq!("00:01:30", time.current_time())
+}
+
+#[test]
+fn test_time_0041() {
+    let (mut time, clock) = mock_time(TimeFormat::HourMinSec);
+
+    time.set_mode(OpMode::ChessClock(ChessClock {
+        time: Duration::from_secs(180),
+        increment: ChessIncrement::Fischer(Duration::from_secs(2)),
+    }));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(30));
+    time.toggle_timer();
+    clock.advance(Duration::from_secs(10));
+
+    let snapshot: TimeSnapshot =
+        toml::from_str(&toml::to_string(&time.snapshot()).unwrap()).unwrap();
+    let (mut restored, clock) = mock_time(TimeFormat::HourMinSec);
+
+    // Restarted 5 seconds after the snapshot was saved
+    clock.advance(Duration::from_secs(45));
+    restored.restore(&snapshot);
+
+    let status = restored.chess().unwrap();
+
+    assert_eq!(Some(ChessSide::Black), status.active);
+    assert_eq!(Duration::from_secs(152), status.white);
+    assert_eq!(Duration::from_secs(165), status.black);
+    assert_eq!((1, 0), (status.white_moves, status.black_moves));
+
+    restored.toggle_timer();
+    clock.advance(Duration::from_secs(2));
+
+    let status = restored.chess().unwrap();
+
+    assert_eq!(Some(ChessSide::White), status.active);
+    assert_eq!(Duration::from_secs(150), status.white);
+    assert_eq!(Duration::from_secs(167), status.black);
+    assert_eq!(1, status.black_moves)
+}
@@ -0,0 +1,415 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{NeedleErr, NeedleError};
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Padding {
+    Zero,
+    Space,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Year,
+    ShortYear,
+    Month,
+    MonthName,
+    ShortMonthName,
+    Day,
+    Weekday,
+    ShortWeekday,
+    Hour,
+    Hour12,
+    AmPm,
+    Minute,
+    Second,
+    Fraction(u32),
+    Days,
+    Overtime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Literal(String),
+    Field(Field, Padding),
+}
+
+/// Parsed form of `TimeFormat::Custom`.
+///
+/// | Specifier | Clock                 | Timer                            |
+/// |-----------|-----------------------|----------------------------------|
+/// | `%H`      | Hour (00-23)          | Hours (wraps at 24 with `%D`)    |
+/// | `%I`      | Hour (01-12)          |                                  |
+/// | `%p`      | AM/PM                 |                                  |
+/// | `%M`      | Minute (00-59)        | Minutes (00-59)                  |
+/// | `%S`      | Second (00-59)        | Seconds (00-59)                  |
+/// | `%1f`     | Tenths of a second    | Tenths of a second               |
+/// | `%2f`     | Hundredths            | Hundredths                       |
+/// | `%3f`/`%f`| Milliseconds          | Milliseconds                     |
+/// | `%D`      |                       | Days                             |
+/// | `%O`      |                       | Overtime prefix                  |
+/// | `%a`/`%A` | Weekday (Mon/Monday)  |                                  |
+/// | `%Y`/`%y` | Year (2025/25)        |                                  |
+/// | `%m`/`%d` | Month/Day (01-12/31)  |                                  |
+/// | `%b`/`%B` | Month (Jan/January)   |                                  |
+/// | `%%`      | Literal `%`           | Literal `%`                      |
+///
+/// The largest of `%D`, `%H`, `%M` and `%S` in a timer pattern carries the
+/// overflow, e.g. `%M:%S` renders 1h02m03s as `62:03`.
+///
+/// Numeric fields are zero padded by default. `%-H` disables padding and
+/// `%_H` pads with spaces instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimePattern {
+    items: Vec<Item>,
+}
+
+impl TimePattern {
+    const WEEKDAYS: [&'static str; 7] = [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ];
+    const MONTHS: [&'static str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+
+    pub fn parse(pattern: &str) -> NeedleErr<Self> {
+        let invalid = |reason: String| -> NeedleError {
+            NeedleError::InvalidTimeFormat(format!("\"{}\": {}", pattern, reason).into())
+        };
+
+        if pattern.is_empty() {
+            return Err(invalid("pattern is empty".to_string()));
+        }
+
+        let mut items = vec![];
+        let mut literal = String::new();
+        let mut chars = pattern.char_indices().peekable();
+
+        while let Some((position, c)) = chars.next() {
+            if c != '%' {
+                literal.push(c);
+                continue;
+            }
+
+            let padding = match chars.next_if(|(_, c)| matches!(c, '-' | '_' | '0')) {
+                Some((_, '-')) => Padding::None,
+                Some((_, '_')) => Padding::Space,
+                _ => Padding::Zero,
+            };
+
+            let field = match chars.next() {
+                Some((_, '%')) => {
+                    literal.push('%');
+                    continue;
+                }
+                Some((_, 'Y')) => Field::Year,
+                Some((_, 'y')) => Field::ShortYear,
+                Some((_, 'm')) => Field::Month,
+                Some((_, 'B')) => Field::MonthName,
+                Some((_, 'b')) => Field::ShortMonthName,
+                Some((_, 'd')) => Field::Day,
+                Some((_, 'A')) => Field::Weekday,
+                Some((_, 'a')) => Field::ShortWeekday,
+                Some((_, 'H')) => Field::Hour,
+                Some((_, 'I')) => Field::Hour12,
+                Some((_, 'p')) => Field::AmPm,
+                Some((_, 'M')) => Field::Minute,
+                Some((_, 'S')) => Field::Second,
+                Some((_, 'D')) => Field::Days,
+                Some((_, 'O')) => Field::Overtime,
+                Some((_, 'f')) => Field::Fraction(3),
+                Some((_, digit @ '1'..='3')) => match chars.next() {
+                    Some((_, 'f')) => Field::Fraction(digit as u32 - '0' as u32),
+                    _ => {
+                        return Err(invalid(format!(
+                            "expected 'f' after \"%{}\" at {}",
+                            digit, position
+                        )))
+                    }
+                },
+                Some((_, other)) => {
+                    return Err(invalid(format!(
+                        "unknown specifier \"%{}\" at {}",
+                        other, position
+                    )))
+                }
+                None => return Err(invalid(format!("dangling '%' at {}", position))),
+            };
+
+            if !literal.is_empty() {
+                items.push(Item::Literal(std::mem::take(&mut literal)));
+            }
+            items.push(Item::Field(field, padding));
+        }
+
+        if !literal.is_empty() {
+            items.push(Item::Literal(literal));
+        }
+
+        Ok(Self { items })
+    }
+
+    pub fn has_days(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| matches!(item, Item::Field(Field::Days, _)))
+    }
+
+    /// Smallest unit displayed, `None` when nothing changes over time
+    pub fn resolution(&self) -> Option<Duration> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Field(Field::Fraction(digits), _) => {
+                    Some(Duration::from_millis(10u64.pow(3 - digits)))
+                }
+                Item::Field(Field::Second, _) => Some(Duration::from_secs(1)),
+                Item::Field(Field::Minute, _) => Some(Duration::from_secs(60)),
+                Item::Field(Field::Hour | Field::Hour12 | Field::AmPm, _) => {
+                    Some(Duration::from_secs(3_600))
+                }
+                Item::Field(Field::Overtime, _) | Item::Literal(_) => None,
+                Item::Field(_, _) => Some(Duration::from_secs(86_400)),
+            })
+            .min()
+    }
+
+    pub fn format_time<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String {
+        let mut output = String::new();
+
+        for item in &self.items {
+            match item {
+                Item::Literal(literal) => output.push_str(literal),
+                Item::Field(field, padding) => {
+                    let nanosecond = time.nanosecond() % 1_000_000_000;
+                    let hour12 = match time.hour() % 12 {
+                        0 => 12,
+                        hour => hour,
+                    };
+                    let text = match field {
+                        Field::Year => Self::pad(time.year() as u64, 4, *padding),
+                        Field::ShortYear => {
+                            Self::pad(time.year().rem_euclid(100) as u64, 2, *padding)
+                        }
+                        Field::Month => Self::pad(time.month() as u64, 2, *padding),
+                        Field::MonthName => Self::MONTHS[time.month0() as usize].to_string(),
+                        Field::ShortMonthName => {
+                            Self::MONTHS[time.month0() as usize][..3].to_string()
+                        }
+                        Field::Day => Self::pad(time.day() as u64, 2, *padding),
+                        Field::Weekday => Self::WEEKDAYS
+                            [time.weekday().num_days_from_monday() as usize]
+                            .to_string(),
+                        Field::ShortWeekday => Self::WEEKDAYS
+                            [time.weekday().num_days_from_monday() as usize][..3]
+                            .to_string(),
+                        Field::Hour => Self::pad(time.hour() as u64, 2, *padding),
+                        Field::Hour12 => Self::pad(hour12 as u64, 2, *padding),
+                        Field::AmPm => if time.hour() < 12 { "AM" } else { "PM" }.to_string(),
+                        Field::Minute => Self::pad(time.minute() as u64, 2, *padding),
+                        Field::Second => Self::pad(time.second() as u64, 2, *padding),
+                        Field::Fraction(digit) => Self::fraction(nanosecond, *digit),
+                        // Only meaningful for timers
+                        Field::Days | Field::Overtime => String::new(),
+                    };
+
+                    output.push_str(&text);
+                }
+            }
+        }
+
+        output
+    }
+
+    pub fn format_duration(&self, duration: &Duration) -> String {
+        self.render_duration(duration, "")
+    }
+
+    /// Formats a countdown which has run past zero. `prefix` is placed at
+    /// `%O`, or in front of the output if the pattern has none.
+    pub fn format_overtime(&self, duration: &Duration, prefix: &str) -> String {
+        let has_overtime = self
+            .items
+            .iter()
+            .any(|item| matches!(item, Item::Field(Field::Overtime, _)));
+
+        if has_overtime {
+            self.render_duration(duration, prefix)
+        } else {
+            format!("{}{}", prefix, self.render_duration(duration, prefix))
+        }
+    }
+
+    fn render_duration(&self, duration: &Duration, prefix: &str) -> String {
+        let secs = duration.as_secs();
+        // Largest unit displayed, which isn't wrapped
+        let largest = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Field(field, _) => Self::unit_secs(field),
+                _ => None,
+            })
+            .max();
+        let value = |unit: u64, wrap: u64| match largest {
+            Some(largest) if largest > unit => (secs / unit) % wrap,
+            _ => secs / unit,
+        };
+        let mut output = String::new();
+
+        for item in &self.items {
+            match item {
+                Item::Literal(literal) => output.push_str(literal),
+                Item::Field(field, padding) => {
+                    let text = match field {
+                        Field::Days => Self::pad(secs / 86_400, 1, *padding),
+                        Field::Hour => Self::pad(value(3_600, 24), 2, *padding),
+                        Field::Minute => Self::pad(value(60, 60), 2, *padding),
+                        Field::Second => Self::pad(value(1, 60), 2, *padding),
+                        Field::Fraction(digit) => Self::fraction(duration.subsec_nanos(), *digit),
+                        Field::Overtime => prefix.to_string(),
+                        // Only meaningful for clocks
+                        _ => String::new(),
+                    };
+
+                    output.push_str(&text);
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Seconds in the unit of a timer field
+    fn unit_secs(field: &Field) -> Option<u64> {
+        match field {
+            Field::Days => Some(86_400),
+            Field::Hour => Some(3_600),
+            Field::Minute => Some(60),
+            Field::Second => Some(1),
+            _ => None,
+        }
+    }
+
+    fn pad(value: u64, width: usize, padding: Padding) -> String {
+        match padding {
+            Padding::Zero => format!("{:0width$}", value, width = width),
+            Padding::Space => format!("{:width$}", value, width = width),
+            Padding::None => value.to_string(),
+        }
+    }
+
+    fn fraction(nanosecond: u32, digit: u32) -> String {
+        let value = nanosecond / 10u32.pow(9 - digit);
+
+        format!("{:0width$}", value, width = digit as usize)
+    }
+}
+
+#[test]
+fn test_pattern_0001() {
+    use chrono::Utc;
+
+    let pattern = TimePattern::parse("%a %Y-%m-%d %I:%M:%S %p").unwrap();
+    let time = Utc.with_ymd_and_hms(2025, 3, 7, 15, 4, 5).unwrap();
+
+    assert_eq!("Fri 2025-03-07 03:04:05 PM", pattern.format_time(&time))
+}
+
+#[test]
+fn test_pattern_0002() {
+    use chrono::Utc;
+
+    let pattern = TimePattern::parse("%-I:%M%% %_H|%A %B %-d %y").unwrap();
+    let time = Utc.with_ymd_and_hms(2025, 12, 1, 0, 30, 0).unwrap();
+
+    assert_eq!("12:30%  0|Monday December 1 25", pattern.format_time(&time))
+}
+
+#[test]
+fn test_pattern_0003() {
+    let duration = Duration::from_millis(((26 * 60 + 5) * 60 + 9) * 1_000 + 876);
+
+    assert_eq!(
+        "26:05:09.8",
+        TimePattern::parse("%H:%M:%S.%1f")
+            .unwrap()
+            .format_duration(&duration)
+    );
+    assert_eq!(
+        "1d 02:05:09.87",
+        TimePattern::parse("%Dd %H:%M:%S.%2f")
+            .unwrap()
+            .format_duration(&duration)
+    );
+    assert_eq!(
+        "1565:09.876",
+        TimePattern::parse("%-M:%S.%f")
+            .unwrap()
+            .format_duration(&duration)
+    )
+}
+
+#[test]
+fn test_pattern_0004() {
+    assert!(TimePattern::parse("").is_err());
+    assert!(TimePattern::parse("%H:%").is_err());
+    assert!(TimePattern::parse("%Q").is_err());
+    assert!(TimePattern::parse("%4f").is_err());
+    assert!(TimePattern::parse("%2x").is_err())
+}
+
+#[test]
+fn test_pattern_0005() {
+    let duration = Duration::from_secs(83);
+
+    assert_eq!(
+        "01:23",
+        TimePattern::parse("%O%M:%S")
+            .unwrap()
+            .format_duration(&duration)
+    );
+    assert_eq!(
+        "over 01:23",
+        TimePattern::parse("%O%M:%S")
+            .unwrap()
+            .format_overtime(&duration, "over ")
+    );
+    assert_eq!(
+        "83",
+        TimePattern::parse("%S").unwrap().format_duration(&duration)
+    );
+    assert_eq!(
+        "-01:23",
+        TimePattern::parse("%M:%S")
+            .unwrap()
+            .format_overtime(&duration, "-")
+    );
+    assert_eq!(
+        "",
+        TimePattern::parse("%O")
+            .unwrap()
+            .format_time(&chrono::Utc::now())
+    )
+}
@@ -0,0 +1,228 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{NeedleErr, NeedleError};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// Phase lengths of `OpMode::Pomodoro`.
+///
+/// `cycles` work phases separated by short breaks are followed by a long
+/// break, after which the set starts over. Phase lengths written as bare
+/// integers are minutes, as in `[pomodoro]` of `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pomodoro {
+    #[serde(deserialize_with = "super::duration::deserialize_minutes")]
+    pub work: Duration,
+    #[serde(deserialize_with = "super::duration::deserialize_minutes")]
+    pub short_break: Duration,
+    #[serde(deserialize_with = "super::duration::deserialize_minutes")]
+    pub long_break: Duration,
+    pub cycles: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PomodoroStatus {
+    pub phase: PomodoroPhase,
+    /// Time left in the current phase
+    pub remaining: Duration,
+    /// Number of finished work phases
+    pub completed: u32,
+}
+
+impl Pomodoro {
+    pub fn validate(&self) -> NeedleErr<()> {
+        match self.set() {
+            Some(_) => Ok(()),
+            None => Err(NeedleError::InvalidPomodoro(
+                "a set of work phases and breaks is too long".into(),
+            )),
+        }
+    }
+
+    /// Resolves the phase reached after running for `elapsed`.
+    pub fn status(&self, elapsed: Duration) -> PomodoroStatus {
+        let cycles = self.cycles.max(1);
+        // Saturated sets are never finished
+        let set = self.set().unwrap_or(Duration::MAX);
+
+        if set.is_zero() {
+            return PomodoroStatus {
+                phase: PomodoroPhase::Work,
+                remaining: Duration::new(0, 0),
+                completed: 0,
+            };
+        }
+
+        let sets = elapsed.as_nanos() / set.as_nanos();
+        let position = elapsed.as_nanos() % set.as_nanos();
+        // Shorter than `set`, which fits in a `Duration`
+        let mut position = Duration::new(
+            (position / 1_000_000_000) as u64,
+            (position % 1_000_000_000) as u32,
+        );
+        let mut completed = u32::try_from(sets)
+            .unwrap_or(u32::MAX)
+            .saturating_mul(cycles);
+
+        for cycle in 1..=cycles {
+            if position < self.work {
+                return PomodoroStatus {
+                    phase: PomodoroPhase::Work,
+                    remaining: self.work - position,
+                    completed,
+                };
+            }
+
+            position -= self.work;
+            completed = completed.saturating_add(1);
+
+            let (phase, length) = if cycle == cycles {
+                (PomodoroPhase::LongBreak, self.long_break)
+            } else {
+                (PomodoroPhase::ShortBreak, self.short_break)
+            };
+
+            if position < length {
+                return PomodoroStatus {
+                    phase,
+                    remaining: length - position,
+                    completed,
+                };
+            }
+
+            position -= length;
+        }
+
+        // Unreachable as `position` is always shorter than a set
+        PomodoroStatus {
+            phase: PomodoroPhase::Work,
+            remaining: self.work,
+            completed,
+        }
+    }
+
+    /// Length of `cycles` work phases with their breaks, `None` on overflow
+    fn set(&self) -> Option<Duration> {
+        let cycles = self.cycles.max(1);
+
+        self.work
+            .checked_mul(cycles)?
+            .checked_add(self.short_break.checked_mul(cycles - 1)?)?
+            .checked_add(self.long_break)
+    }
+}
+
+impl Default for Pomodoro {
+    fn default() -> Self {
+        Self {
+            work: Duration::from_secs(25 * 60),
+            short_break: Duration::from_secs(5 * 60),
+            long_break: Duration::from_secs(15 * 60),
+            cycles: 4,
+        }
+    }
+}
+
+impl Display for PomodoroPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let phase = match self {
+            Self::Work => "Work",
+            Self::ShortBreak => "Short Break",
+            Self::LongBreak => "Long Break",
+        };
+
+        write!(f, "{}", phase)
+    }
+}
+
+#[test]
+fn test_pomodoro_0001() {
+    let pomodoro = Pomodoro {
+        work: Duration::from_secs(10),
+        short_break: Duration::from_secs(2),
+        long_break: Duration::from_secs(5),
+        cycles: 2,
+    };
+    let status = |secs| pomodoro.status(Duration::from_secs(secs));
+    let expected = |phase, remaining, completed| PomodoroStatus {
+        phase,
+        remaining: Duration::from_secs(remaining),
+        completed,
+    };
+
+    assert_eq!(expected(PomodoroPhase::Work, 10, 0), status(0));
+    assert_eq!(expected(PomodoroPhase::ShortBreak, 2, 1), status(10));
+    assert_eq!(expected(PomodoroPhase::Work, 9, 1), status(13));
+    assert_eq!(expected(PomodoroPhase::LongBreak, 4, 2), status(23));
+    // Next set
+    assert_eq!(expected(PomodoroPhase::Work, 10, 2), status(27));
+    assert_eq!(expected(PomodoroPhase::ShortBreak, 1, 3), status(38))
+}
+
+#[test]
+fn test_pomodoro_0002() {
+    let pomodoro = Pomodoro {
+        work: Duration::new(0, 0),
+        short_break: Duration::new(0, 0),
+        long_break: Duration::new(0, 0),
+        cycles: 0,
+    };
+
+    assert_eq!(
+        PomodoroPhase::Work,
+        pomodoro.status(Duration::from_secs(5)).phase
+    )
+}
+
+#[test]
+fn test_pomodoro_0003() {
+    let pomodoro = Pomodoro {
+        work: Duration::MAX,
+        short_break: Duration::from_secs(1),
+        long_break: Duration::from_secs(1),
+        cycles: 2,
+    };
+
+    assert!(matches!(
+        pomodoro.validate(),
+        Err(NeedleError::InvalidPomodoro(_))
+    ));
+    assert_eq!(
+        PomodoroPhase::Work,
+        pomodoro.status(Duration::from_secs(5)).phase
+    );
+    assert!(Pomodoro::default().validate().is_ok());
+
+    // Far more sets than `u32` holds
+    let pomodoro = Pomodoro {
+        work: Duration::new(0, 1),
+        short_break: Duration::new(0, 0),
+        long_break: Duration::new(0, 0),
+        cycles: 1,
+    };
+
+    assert_eq!(u32::MAX, pomodoro.status(Duration::MAX).completed)
+}
+
+#[test]
+fn test_pomodoro_0004() {
+    let pomodoro: Pomodoro =
+        toml::from_str("work = 25\nshort_break = \"300s\"\nlong_break = 15\ncycles = 4").unwrap();
+
+    assert_eq!(Pomodoro::default(), pomodoro);
+    assert_eq!(
+        pomodoro,
+        toml::from_str(&toml::to_string(&pomodoro).unwrap()).unwrap()
+    )
+}
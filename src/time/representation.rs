@@ -0,0 +1,210 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{Fuzziness, Locale};
+use chrono::{DateTime, SecondsFormat, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+/// How the time of day is rendered by clock modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Representation {
+    /// Digits laid out by `TimeFormat`
+    #[default]
+    Clock,
+    /// Seconds since 1970-01-01T00:00:00Z
+    UnixSeconds,
+    /// Milliseconds since 1970-01-01T00:00:00Z
+    UnixMillis,
+    /// e.g. `2025-01-01T21:34:56+09:00`
+    Iso8601,
+    /// Days since noon of 4713-01-01 BC (proleptic Julian calendar), with fraction
+    JulianDay,
+    /// French decimal time: 10 hours of 100 minutes of 100 seconds a day
+    DecimalTime,
+    /// Hour, minute and second in binary
+    Binary,
+    /// Hour, minute and second in hexadecimal
+    Hex,
+    /// e.g. "quarter past ten", "十時十五分"
+    Words {
+        #[serde(default)]
+        locale: Locale,
+        #[serde(default)]
+        fuzziness: Fuzziness,
+    },
+}
+
+impl Representation {
+    const SECONDS_PER_DAY: f64 = 86_400.0;
+    /// Julian day of 1970-01-01T00:00:00Z
+    const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+
+    /// Renders `time`. Returns `None` for `Representation::Clock` which is
+    /// left to `TimeFormat`.
+    pub fn format<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> Option<String>
+    where
+        Tz::Offset: Display,
+    {
+        match self {
+            Self::Clock => None,
+            Self::UnixSeconds => Some(time.timestamp().to_string()),
+            Self::UnixMillis => Some(time.timestamp_millis().to_string()),
+            Self::Iso8601 => Some(time.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            Self::JulianDay => Some(format!("{:.5}", Self::julian_day(time))),
+            Self::DecimalTime => Some(Self::decimal_time(time)),
+            Self::Binary => Some(format!(
+                "{:05b}:{:06b}:{:06b}",
+                time.hour(),
+                time.minute(),
+                time.second()
+            )),
+            Self::Hex => Some(format!(
+                "{:02X}:{:02X}:{:02X}",
+                time.hour(),
+                time.minute(),
+                time.second()
+            )),
+            Self::Words { locale, fuzziness } => {
+                let (hour, minute) = fuzziness.round(time.hour(), time.minute(), time.second());
+
+                Some(locale.words().words(hour, minute))
+            }
+        }
+    }
+
+    /// Smallest unit displayed, `None` for `Representation::Clock`
+    pub fn resolution(&self) -> Option<Duration> {
+        match self {
+            Self::Clock => None,
+            Self::UnixMillis => Some(Duration::from_millis(1)),
+            // 0.00001 days and decimal seconds
+            Self::JulianDay | Self::DecimalTime => Some(Duration::from_millis(864)),
+            // Rounding to the nearest step changes half way through a minute
+            Self::Words { .. } => Some(Duration::from_secs(30)),
+            _ => Some(Duration::from_secs(1)),
+        }
+    }
+
+    /// Whether units are counted from the Unix epoch rather than local midnight
+    pub fn is_epoch_aligned(&self) -> bool {
+        matches!(self, Self::UnixSeconds | Self::UnixMillis | Self::JulianDay)
+    }
+
+    fn julian_day<Tz: TimeZone>(time: &DateTime<Tz>) -> f64 {
+        let millis = time.timestamp_millis() as f64;
+
+        millis / (Self::SECONDS_PER_DAY * 1_000.0) + Self::UNIX_EPOCH_JULIAN_DAY
+    }
+
+    fn decimal_time<Tz: TimeZone>(time: &DateTime<Tz>) -> String {
+        // Decimal seconds are 0.864 seconds long
+        let millis = time.num_seconds_from_midnight() as u64 * 1_000
+            + (time.nanosecond().min(999_999_999) / 1_000_000) as u64;
+        let decimal = millis * 100_000 / 86_400_000;
+
+        format!(
+            "{}:{:02}:{:02}",
+            decimal / 10_000,
+            (decimal / 100) % 100,
+            decimal % 100
+        )
+    }
+}
+
+impl Display for Representation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let representation = match self {
+            Self::Clock => "Clock",
+            Self::UnixSeconds => "UnixSeconds",
+            Self::UnixMillis => "UnixMillis",
+            Self::Iso8601 => "Iso8601",
+            Self::JulianDay => "JulianDay",
+            Self::DecimalTime => "DecimalTime",
+            Self::Binary => "Binary",
+            Self::Hex => "Hex",
+            Self::Words { .. } => "Words",
+        };
+
+        write!(f, "{}", representation)
+    }
+}
+
+#[test]
+fn test_representation_0001() {
+    use chrono::{FixedOffset, Utc};
+
+    let utc = Utc.with_ymd_and_hms(2025, 1, 1, 12, 34, 56).unwrap();
+    let tokyo = utc.with_timezone(&FixedOffset::east_opt(9 * 3_600).unwrap());
+    let format = |representation: Representation| representation.format(&utc).unwrap();
+
+    assert_eq!(None, Representation::Clock.format(&utc));
+    assert_eq!("1735734896", format(Representation::UnixSeconds));
+    assert_eq!("1735734896000", format(Representation::UnixMillis));
+    assert_eq!("2025-01-01T12:34:56Z", format(Representation::Iso8601));
+    assert_eq!(
+        Some("2025-01-01T21:34:56+09:00".to_string()),
+        Representation::Iso8601.format(&tokyo)
+    );
+    // Noon of 2025-01-01 is JD 2460677.0
+    assert_eq!("2460677.02426", format(Representation::JulianDay));
+    assert_eq!("5:24:25", format(Representation::DecimalTime));
+    assert_eq!("01100:100010:111000", format(Representation::Binary));
+    assert_eq!("0C:22:38", format(Representation::Hex));
+    assert_eq!(
+        Some("15:22:38".to_string()),
+        Representation::Hex.format(&tokyo)
+    )
+}
+
+#[test]
+fn test_representation_0002() {
+    use chrono::Utc;
+
+    for ((hour, minute, second), expected) in [
+        ((0, 0, 0), "0:00:00"),
+        ((6, 0, 0), "2:50:00"),
+        ((12, 0, 0), "5:00:00"),
+        ((18, 0, 0), "7:50:00"),
+        ((23, 59, 59), "9:99:98"),
+    ] {
+        let time = Utc
+            .with_ymd_and_hms(2025, 1, 1, hour, minute, second)
+            .unwrap();
+
+        assert_eq!(
+            Some(expected.to_string()),
+            Representation::DecimalTime.format(&time)
+        )
+    }
+
+    // Start of the Unix epoch in Julian days
+    assert_eq!(
+        Some("2440587.50000".to_string()),
+        Representation::JulianDay.format(&DateTime::UNIX_EPOCH)
+    )
+}
+
+#[test]
+fn test_representation_0003() {
+    use chrono::Utc;
+
+    let time = Utc.with_ymd_and_hms(2025, 1, 1, 10, 13, 0).unwrap();
+    let words = |locale, fuzziness| Representation::Words { locale, fuzziness }.format(&time);
+
+    assert_eq!(
+        Some("thirteen minutes past ten".to_string()),
+        words(Locale::English, Fuzziness::Exact)
+    );
+    assert_eq!(
+        Some("quarter past ten".to_string()),
+        words(Locale::English, Fuzziness::FiveMinutes)
+    );
+    assert_eq!(
+        Some("十時十五分".to_string()),
+        words(Locale::Japanese, Fuzziness::QuarterHour)
+    )
+}
@@ -0,0 +1,386 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{NeedleConfig, NeedleErr, NeedleError};
+use serde::{Deserialize, Serialize};
+use std::{path::Path, time::Duration};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub label: String,
+    #[serde(deserialize_with = "super::duration::deserialize_duration")]
+    pub length: Duration,
+    /// Text color : [r, g, b, alpha]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 4]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Step {
+    Segment(Segment),
+    Repeat { repeat: u32, steps: Vec<Step> },
+}
+
+/// Chained timers run by `OpMode::Sequence`.
+///
+/// ```toml
+/// name = "Tabata"
+///
+/// [[steps]]
+/// repeat = 8
+/// steps = [
+///     { label = "Work", length = "20s", color = [255, 64, 64, 255] },
+///     { label = "Rest", length = "10s" },
+/// ]
+///
+/// [[steps]]
+/// label = "Cooldown"
+/// length = "1m"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sequence {
+    #[serde(default)]
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceStatus {
+    /// Index of the current segment with repeats expanded
+    pub index: usize,
+    pub count: usize,
+    pub label: String,
+    pub color: Option<[u8; 4]>,
+    /// Time left in the current segment
+    pub remaining: Duration,
+    pub finished: bool,
+}
+
+impl Sequence {
+    const SEQUENCE_FILE: &'static str = "sequence.toml";
+    /// Upper bound of segments with repeats expanded
+    const MAX_SEGMENTS: usize = 10_000;
+
+    pub fn from(path: Option<&str>) -> NeedleErr<Self> {
+        let default_sequence_file = NeedleConfig::config_path(false, Some(Self::SEQUENCE_FILE))?;
+        let sequence_file = match path {
+            Some(path) if !path.is_empty() => Path::new(path),
+            _ => &default_sequence_file,
+        };
+
+        if !sequence_file.exists() {
+            let sequence_file = sequence_file.to_string_lossy();
+
+            return Err(NeedleError::ConfigNonExistant(sequence_file.into()));
+        }
+
+        let sequence: Self = NeedleConfig::read(sequence_file)?;
+
+        sequence.validate()?;
+
+        Ok(sequence)
+    }
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        fn validate_steps(steps: &[Step]) -> NeedleErr<()> {
+            if steps.is_empty() {
+                return Err(NeedleError::InvalidSequence("steps are empty".into()));
+            }
+
+            steps.iter().try_for_each(|step| match step {
+                Step::Segment(_) => Ok(()),
+                Step::Repeat { repeat: 0, .. } => Err(NeedleError::InvalidSequence(
+                    "repeat must be 1 or more".into(),
+                )),
+                Step::Repeat { steps, .. } => validate_steps(steps),
+            })
+        }
+
+        validate_steps(&self.steps)?;
+
+        match Self::count(&self.steps) {
+            Some(count) if count <= Self::MAX_SEGMENTS => Ok(()),
+            _ => Err(NeedleError::InvalidSequence(
+                format!("more than {} segments", Self::MAX_SEGMENTS).into(),
+            )),
+        }?;
+
+        match Self::length(&self.steps) {
+            Some(_) => Ok(()),
+            None => Err(NeedleError::InvalidSequence("sequence is too long".into())),
+        }
+    }
+
+    /// Segments in running order with repeats expanded
+    pub fn segments(&self) -> Vec<Segment> {
+        fn expand(steps: &[Step], segments: &mut Vec<Segment>) {
+            for step in steps {
+                match step {
+                    Step::Segment(segment) => segments.push(segment.clone()),
+                    Step::Repeat { repeat, steps } => {
+                        for _ in 0..*repeat {
+                            expand(steps, segments);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut segments = vec![];
+
+        expand(&self.steps, &mut segments);
+
+        segments
+    }
+
+    pub fn duration(&self) -> Duration {
+        Self::length(&self.steps).unwrap_or(Duration::MAX)
+    }
+
+    /// Resolves the segment reached after running for `elapsed`.
+    /// Returns `None` for a sequence without segments.
+    ///
+    /// Repeats are skipped over arithmetically rather than expanded, as this
+    /// is called several times per frame.
+    pub fn status(&self, elapsed: Duration) -> Option<SequenceStatus> {
+        let count = Self::count(&self.steps).unwrap_or(usize::MAX);
+        let mut position = elapsed;
+        let mut index = 0;
+
+        if let Some(segment) = Self::locate(&self.steps, &mut position, &mut index) {
+            return Some(SequenceStatus {
+                index,
+                count,
+                label: segment.label.clone(),
+                color: segment.color,
+                remaining: segment.length - position,
+                finished: false,
+            });
+        }
+
+        Self::last(&self.steps).map(|segment| SequenceStatus {
+            index: count - 1,
+            count,
+            label: segment.label.clone(),
+            color: segment.color,
+            remaining: Duration::new(0, 0),
+            finished: true,
+        })
+    }
+
+    /// Number of segments with repeats expanded, `None` on overflow
+    fn count(steps: &[Step]) -> Option<usize> {
+        steps.iter().try_fold(0usize, |count, step| {
+            let step = match step {
+                Step::Segment(_) => Some(1),
+                Step::Repeat { repeat, steps } => Self::count(steps)?.checked_mul(*repeat as usize),
+            };
+
+            count.checked_add(step?)
+        })
+    }
+
+    /// Length with repeats expanded, `None` on overflow
+    fn length(steps: &[Step]) -> Option<Duration> {
+        steps.iter().try_fold(Duration::new(0, 0), |length, step| {
+            let step = match step {
+                Step::Segment(segment) => Some(segment.length),
+                Step::Repeat { repeat, steps } => Self::length(steps)?.checked_mul(*repeat),
+            };
+
+            length.checked_add(step?)
+        })
+    }
+
+    /// Segment `position` falls in, leaving `position` relative to its start
+    /// and `index` at its index. `None` once every step has been passed.
+    fn locate<'a>(
+        steps: &'a [Step],
+        position: &mut Duration,
+        index: &mut usize,
+    ) -> Option<&'a Segment> {
+        for step in steps {
+            match step {
+                Step::Segment(segment) => {
+                    if *position < segment.length {
+                        return Some(segment);
+                    }
+
+                    *position -= segment.length;
+                    *index = index.saturating_add(1);
+                }
+                Step::Repeat { repeat, steps } => {
+                    let period = Self::length(steps).unwrap_or(Duration::MAX);
+                    let count = Self::count(steps).unwrap_or(usize::MAX);
+
+                    match period.checked_mul(*repeat) {
+                        Some(total) if *position >= total => {
+                            *position -= total;
+                            *index = index.saturating_add(count.saturating_mul(*repeat as usize));
+                        }
+                        _ => {
+                            // Fewer than `repeat` as `position` is within the
+                            // repeat, and `period` isn't zero
+                            let repeated = (position.as_nanos() / period.as_nanos()) as u32;
+
+                            *position -= period * repeated;
+                            *index = index.saturating_add(count.saturating_mul(repeated as usize));
+
+                            return Self::locate(steps, position, index);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn last(steps: &[Step]) -> Option<&Segment> {
+        steps.iter().rev().find_map(|step| match step {
+            Step::Segment(segment) => Some(segment),
+            Step::Repeat { steps, .. } => Self::last(steps),
+        })
+    }
+}
+
+#[cfg(test)]
+const TABATA: &str = r#"
+name = "Tabata"
+
+[[steps]]
+repeat = 8
+steps = [
+    { label = "Work", length = "20s", color = [255, 64, 64, 255] },
+    { label = "Rest", length = 10 },
+]
+
+[[steps]]
+label = "Cooldown"
+length = "1m"
+"#;
+
+#[test]
+fn test_sequence_0001() {
+    let sequence: Sequence = toml::from_str(TABATA).unwrap();
+    let segments = sequence.segments();
+
+    assert!(sequence.validate().is_ok());
+    assert_eq!("Tabata", sequence.name);
+    assert_eq!(17, segments.len());
+    assert_eq!("Cooldown", segments[16].label);
+    assert_eq!(Duration::from_secs(8 * 30 + 60), sequence.duration())
+}
+
+#[test]
+fn test_sequence_0002() {
+    let sequence: Sequence = toml::from_str(TABATA).unwrap();
+    let status = |secs| sequence.status(Duration::from_secs(secs)).unwrap();
+
+    assert_eq!(
+        SequenceStatus {
+            index: 0,
+            count: 17,
+            label: "Work".to_string(),
+            color: Some([255, 64, 64, 255]),
+            remaining: Duration::from_secs(20),
+            finished: false,
+        },
+        status(0)
+    );
+    assert_eq!(("Rest", 3), (status(55).label.as_str(), status(55).index));
+    assert_eq!(Duration::from_secs(5), status(295).remaining);
+    assert!(status(300).finished);
+    assert_eq!(Duration::new(0, 0), status(300).remaining)
+}
+
+#[test]
+fn test_sequence_0003() {
+    let empty = Sequence {
+        name: String::new(),
+        steps: vec![],
+    };
+    let zero_repeat: Sequence =
+        toml::from_str("[[steps]]\nrepeat = 0\nsteps = [{ label = \"A\", length = 1 }]").unwrap();
+
+    assert!(empty.validate().is_err());
+    assert_eq!(None, empty.status(Duration::new(0, 0)));
+    assert!(zero_repeat.validate().is_err())
+}
+
+#[test]
+fn test_sequence_0004() {
+    let path = std::env::temp_dir().join("needle_test_sequence_0004.toml");
+
+    std::fs::write(&path, TABATA).unwrap();
+
+    let sequence = Sequence::from(path.to_str());
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(17, sequence.unwrap().segments().len());
+    assert!(Sequence::from(Some("/nonexistent/sequence.toml")).is_err())
+}
+
+#[test]
+fn test_sequence_0005() {
+    let nested: Sequence = toml::from_str(
+        r#"
+[[steps]]
+repeat = 3
+steps = [
+    { label = "Warmup", length = 5 },
+    { repeat = 2, steps = [{ label = "Work", length = 20 }, { label = "Rest", length = 10 }] },
+]
+
+[[steps]]
+label = "Cooldown"
+length = 60
+"#,
+    )
+    .unwrap();
+    let segments = nested.segments();
+
+    assert!(nested.validate().is_ok());
+    assert_eq!(Duration::from_secs(3 * 65 + 60), nested.duration());
+
+    // Same as walking the expanded segments
+    let mut start = Duration::new(0, 0);
+
+    for (index, segment) in segments.iter().enumerate() {
+        for offset in [0, 1, segment.length.as_secs() - 1] {
+            let status = nested.status(start + Duration::from_secs(offset)).unwrap();
+
+            assert_eq!(
+                (index, segment.label.as_str()),
+                (status.index, status.label.as_str())
+            );
+            assert_eq!(
+                Duration::from_secs(segment.length.as_secs() - offset),
+                status.remaining
+            );
+        }
+        start += segment.length;
+    }
+    assert_eq!(15, nested.status(start).unwrap().index);
+    assert!(nested.status(start).unwrap().finished);
+}
+
+#[test]
+fn test_sequence_0006() {
+    let huge: Sequence = toml::from_str(
+        "[[steps]]\nrepeat = 4294967295\nsteps = [{ repeat = 4294967295, steps = [{ label = \"A\", length = 1 }] }]",
+    )
+    .unwrap();
+
+    assert!(matches!(
+        huge.validate(),
+        Err(NeedleError::InvalidSequence(_))
+    ));
+
+    // Resolved without expanding the repeats
+    let status = huge.status(Duration::from_secs(1_000_000_007)).unwrap();
+
+    assert_eq!(1_000_000_007, status.index);
+    assert_eq!(Duration::from_secs(1), status.remaining)
+}
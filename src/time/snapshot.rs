@@ -0,0 +1,136 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::{ChessSnapshot, Lap, OpMode, TimeFormat};
+use crate::{NeedleConfig, NeedleErr, NeedleError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Serializable state of `Time`, saved next to `config.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSnapshot {
+    pub mode: OpMode,
+    pub format: TimeFormat,
+    pub elapsed: Duration,
+    pub running: bool,
+    /// Wall-clock time the snapshot was taken at
+    pub saved_at: DateTime<Utc>,
+    pub laps: Vec<Lap>,
+    /// Game in progress of `OpMode::ChessClock`
+    #[serde(default)]
+    pub chess: Option<ChessSnapshot>,
+}
+
+impl TimeSnapshot {
+    const SNAPSHOT_FILE: &'static str = "timer.toml";
+
+    pub fn save(&self) -> NeedleErr<()> {
+        self.save_to(&Self::snapshot_file(true)?)
+    }
+
+    /// Returns `None` if no snapshot has been saved
+    pub fn load() -> NeedleErr<Option<Self>> {
+        Self::load_from(&Self::snapshot_file(false)?)
+    }
+
+    pub fn remove() -> NeedleErr<()> {
+        let file = Self::snapshot_file(false)?;
+
+        if !file.exists() {
+            return Ok(());
+        }
+
+        match fs::remove_file(file) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> NeedleErr<()> {
+        let snapshot = match toml::to_string(self) {
+            Ok(snapshot) => Ok(snapshot),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }?;
+        let file = match OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+        {
+            Ok(file) => Ok(file),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }?;
+        let mut buf_writer = BufWriter::new(file);
+
+        match write!(buf_writer, "{}", snapshot) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(NeedleError::FailedToWriteSnapshot(err.into())),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> NeedleErr<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let snapshot = match fs::read_to_string(path) {
+            Ok(snapshot) => Ok(snapshot),
+            Err(err) => Err(NeedleError::FailedToReadSnapshot(err.into())),
+        }?;
+
+        match toml::from_str(&snapshot) {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(err) => Err(NeedleError::FailedToReadSnapshot(err.into())),
+        }
+    }
+
+    fn snapshot_file(create_dir: bool) -> NeedleErr<PathBuf> {
+        NeedleConfig::config_path(create_dir, Some(Self::SNAPSHOT_FILE))
+    }
+}
+
+#[test]
+fn test_snapshot_0001() {
+    use chrono::TimeZone;
+
+    let snapshot = TimeSnapshot {
+        mode: OpMode::CountDownTimer(Duration::from_secs(90)),
+        format: TimeFormat::Custom("%M:%S".to_string()),
+        elapsed: Duration::from_millis(12_345),
+        running: true,
+        saved_at: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        laps: vec![Lap {
+            split: Duration::from_secs(3),
+            total: Duration::from_secs(3),
+        }],
+        chess: None,
+    };
+    let path = std::env::temp_dir().join("needle_test_snapshot_0001.toml");
+
+    assert_eq!(None, TimeSnapshot::load_from(&path).unwrap());
+
+    snapshot.save_to(&path).unwrap();
+
+    let loaded = TimeSnapshot::load_from(&path);
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(Some(snapshot), loaded.unwrap())
+}
+
+#[test]
+fn test_snapshot_0002() {
+    let path = std::env::temp_dir().join("needle_test_snapshot_0002.toml");
+
+    fs::write(&path, "mode = \"Sundial\"").unwrap();
+
+    let loaded = TimeSnapshot::load_from(&path);
+
+    fs::remove_file(&path).unwrap();
+    assert!(loaded.is_err())
+}
@@ -0,0 +1,327 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::Zone;
+use crate::{NeedleErr, NeedleError};
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Position on earth solar events are calculated for, in degrees.
+/// North and east are positive.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Daily events of the sun, in the order they happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SolarEvent {
+    AstronomicalDawn,
+    NauticalDawn,
+    CivilDawn,
+    Sunrise,
+    SolarNoon,
+    Sunset,
+    CivilDusk,
+    NauticalDusk,
+    AstronomicalDusk,
+}
+
+impl SolarEvent {
+    pub const ALL: [Self; 9] = [
+        Self::AstronomicalDawn,
+        Self::NauticalDawn,
+        Self::CivilDawn,
+        Self::Sunrise,
+        Self::SolarNoon,
+        Self::Sunset,
+        Self::CivilDusk,
+        Self::NauticalDusk,
+        Self::AstronomicalDusk,
+    ];
+
+    /// Zenith angle of the sun at the event in degrees, `None` for solar noon.
+    /// Sunrise and sunset account for refraction and the radius of the sun.
+    const fn zenith(&self) -> Option<f64> {
+        match self {
+            Self::Sunrise | Self::Sunset => Some(90.833),
+            Self::CivilDawn | Self::CivilDusk => Some(96.0),
+            Self::NauticalDawn | Self::NauticalDusk => Some(102.0),
+            Self::AstronomicalDawn | Self::AstronomicalDusk => Some(108.0),
+            Self::SolarNoon => None,
+        }
+    }
+
+    const fn is_rising(&self) -> bool {
+        matches!(
+            self,
+            Self::AstronomicalDawn | Self::NauticalDawn | Self::CivilDawn | Self::Sunrise
+        )
+    }
+}
+
+impl Coordinates {
+    /// Days searched ahead for the next event, covering polar day and night
+    pub const LOOKAHEAD_DAYS: u64 = 366;
+    /// Iterations refining the position of the sun at the event
+    const ITERATIONS: usize = 3;
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        let invalid = |reason: &str| {
+            NeedleError::InvalidCoordinates(
+                format!("{}, {}: {}", self.latitude, self.longitude, reason).into(),
+            )
+        };
+
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            Err(invalid("latitude must be within -90 and 90"))
+        } else if !(-180.0..=180.0).contains(&self.longitude) {
+            Err(invalid("longitude must be within -180 and 180"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Time of `event` on the solar day around `date`. `None` when the sun
+    /// doesn't reach the angle of the event, e.g. sunrise in polar night.
+    pub fn event_time(&self, date: NaiveDate, event: SolarEvent) -> Option<DateTime<Utc>> {
+        let midnight = date.and_time(NaiveTime::MIN).and_utc();
+        // Minutes after midnight UTC, refined with the sun at the last estimate
+        let mut minutes = 720.0 - 4.0 * self.longitude;
+
+        for _ in 0..Self::ITERATIONS {
+            let (declination, equation_of_time) = Self::sun(&midnight, minutes);
+            let hour_angle = match event.zenith() {
+                Some(zenith) => {
+                    let latitude = self.latitude.to_radians();
+                    let cos = zenith.to_radians().cos() / (latitude.cos() * declination.cos())
+                        - latitude.tan() * declination.tan();
+
+                    if !(-1.0..=1.0).contains(&cos) {
+                        return None;
+                    }
+                    cos.acos().to_degrees()
+                }
+                None => 0.0,
+            };
+            let hour_angle = if event.is_rising() {
+                hour_angle
+            } else {
+                -hour_angle
+            };
+
+            minutes = 720.0 - 4.0 * (self.longitude + hour_angle) - equation_of_time;
+        }
+
+        Some(midnight + TimeDelta::milliseconds((minutes * 60_000.0).round() as i64))
+    }
+
+    /// Events of the solar day around `date` in order, skipping the ones not
+    /// happening on that day
+    pub fn solar_day(&self, date: NaiveDate) -> Vec<(SolarEvent, DateTime<Utc>)> {
+        SolarEvent::ALL
+            .iter()
+            .filter_map(|event| self.event_time(date, *event).map(|time| (*event, time)))
+            .collect()
+    }
+
+    /// First event after `after`, with solar days counted by the dates of `zone`
+    pub fn next_event(
+        &self,
+        zone: Zone,
+        after: &DateTime<Utc>,
+    ) -> Option<(SolarEvent, DateTime<Utc>)> {
+        // Events of the previous local date may still be ahead in UTC
+        let today = zone.to_local(after).date().checked_sub_days(Days::new(1))?;
+
+        (0..=Self::LOOKAHEAD_DAYS + 1)
+            .filter_map(|days| today.checked_add_days(Days::new(days)))
+            .flat_map(|date| self.solar_day(date))
+            .find(|(_, time)| time > after)
+    }
+
+    /// Declination in radians and equation of time in minutes of the sun,
+    /// `minutes` after `midnight`
+    fn sun(midnight: &DateTime<Utc>, minutes: f64) -> (f64, f64) {
+        let julian_day = midnight.timestamp() as f64 / 86_400.0 + 2_440_587.5 + minutes / 1_440.0;
+        let t = (julian_day - 2_451_545.0) / 36_525.0;
+
+        let mean_longitude = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0);
+        let mean_anomaly = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+        let eccentricity = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+        let anomaly = mean_anomaly.to_radians();
+        let center = anomaly.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+            + (2.0 * anomaly).sin() * (0.019993 - 0.000101 * t)
+            + (3.0 * anomaly).sin() * 0.000289;
+        let omega = (125.04 - 1934.136 * t).to_radians();
+        let apparent_longitude =
+            (mean_longitude + center - 0.00569 - 0.00478 * omega.sin()).to_radians();
+        let mean_obliquity =
+            23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+        let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+
+        let declination = (obliquity.sin() * apparent_longitude.sin()).asin();
+
+        let y = (obliquity / 2.0).tan().powi(2);
+        let longitude = mean_longitude.to_radians();
+        let equation_of_time = y * (2.0 * longitude).sin() - 2.0 * eccentricity * anomaly.sin()
+            + 4.0 * eccentricity * y * anomaly.sin() * (2.0 * longitude).cos()
+            - 0.5 * y * y * (4.0 * longitude).sin()
+            - 1.25 * eccentricity * eccentricity * (2.0 * anomaly).sin();
+
+        (declination, 4.0 * equation_of_time.to_degrees())
+    }
+}
+
+impl Display for Coordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ latitude = {:?}, longitude = {:?} }}",
+            self.latitude, self.longitude
+        )
+    }
+}
+
+impl Display for SolarEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let event = match self {
+            Self::AstronomicalDawn => "Astronomical dawn",
+            Self::NauticalDawn => "Nautical dawn",
+            Self::CivilDawn => "Civil dawn",
+            Self::Sunrise => "Sunrise",
+            Self::SolarNoon => "Solar noon",
+            Self::Sunset => "Sunset",
+            Self::CivilDusk => "Civil dusk",
+            Self::NauticalDusk => "Nautical dusk",
+            Self::AstronomicalDusk => "Astronomical dusk",
+        };
+
+        write!(f, "{}", event)
+    }
+}
+
+#[cfg(test)]
+fn assert_near(expected: &str, actual: Option<DateTime<Utc>>) {
+    let expected = DateTime::parse_from_rfc3339(expected)
+        .unwrap()
+        .with_timezone(&Utc);
+    let actual = actual.unwrap();
+
+    // Almanacs round to the minute
+    assert!(
+        (actual - expected).num_seconds().abs() <= 60,
+        "expected {}, got {}",
+        expected,
+        actual
+    )
+}
+
+#[test]
+fn test_solar_0001() {
+    // London, times rounded to the minute as in almanac tables
+    let london = Coordinates {
+        latitude: 51.5074,
+        longitude: -0.1278,
+    };
+    let date = NaiveDate::from_ymd_opt(2025, 6, 21).unwrap();
+    let time = |event| london.event_time(date, event);
+
+    assert_near("2025-06-21T03:43:00Z", time(SolarEvent::Sunrise));
+    assert_near("2025-06-21T12:02:00Z", time(SolarEvent::SolarNoon));
+    assert_near("2025-06-21T20:21:00Z", time(SolarEvent::Sunset));
+    assert_near("2025-06-21T02:55:00Z", time(SolarEvent::CivilDawn));
+    assert_near("2025-06-21T21:09:00Z", time(SolarEvent::CivilDusk));
+    assert_near("2025-06-21T01:41:00Z", time(SolarEvent::NauticalDawn));
+    assert_near("2025-06-21T22:24:00Z", time(SolarEvent::NauticalDusk));
+    // The sun stays above -18° around the summer solstice
+    assert_eq!(None, time(SolarEvent::AstronomicalDawn));
+    assert_eq!(None, time(SolarEvent::AstronomicalDusk));
+    assert_eq!(7, london.solar_day(date).len())
+}
+
+#[test]
+fn test_solar_0002() {
+    // Tokyo
+    let tokyo = Coordinates {
+        latitude: 35.6812,
+        longitude: 139.7671,
+    };
+    let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let time = |event| tokyo.event_time(date, event);
+
+    assert_near("2024-12-31T21:51:00Z", time(SolarEvent::Sunrise));
+    assert_near("2025-01-01T02:44:00Z", time(SolarEvent::SolarNoon));
+    assert_near("2025-01-01T07:38:00Z", time(SolarEvent::Sunset));
+    assert_near("2024-12-31T20:19:00Z", time(SolarEvent::AstronomicalDawn));
+    assert_near("2025-01-01T09:10:00Z", time(SolarEvent::AstronomicalDusk));
+
+    let day = tokyo.solar_day(date);
+
+    assert_eq!(
+        SolarEvent::ALL.to_vec(),
+        day.iter().map(|(event, _)| *event).collect::<Vec<_>>()
+    );
+    assert!(day.windows(2).all(|events| events[0].1 < events[1].1))
+}
+
+#[test]
+fn test_solar_0003() {
+    // Tromsø
+    let tromso = Coordinates {
+        latitude: 69.6492,
+        longitude: 18.9553,
+    };
+    let winter = NaiveDate::from_ymd_opt(2025, 12, 21).unwrap();
+    let summer = NaiveDate::from_ymd_opt(2025, 6, 21).unwrap();
+
+    // Polar night still has twilight around noon
+    assert_eq!(None, tromso.event_time(winter, SolarEvent::Sunrise));
+    assert_eq!(None, tromso.event_time(winter, SolarEvent::Sunset));
+    assert!(tromso.event_time(winter, SolarEvent::CivilDawn).is_some());
+    // Midnight sun
+    assert_eq!(
+        vec![SolarEvent::SolarNoon],
+        tromso
+            .solar_day(summer)
+            .into_iter()
+            .map(|(event, _)| event)
+            .collect::<Vec<_>>()
+    )
+}
+
+#[test]
+fn test_solar_0004() {
+    let tokyo = Coordinates {
+        latitude: 35.6812,
+        longitude: 139.7671,
+    };
+    let zone = Zone::parse("Asia/Tokyo").unwrap();
+    let after = DateTime::parse_from_rfc3339("2025-01-01T12:34:56Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let (event, time) = tokyo.next_event(zone, &after).unwrap();
+
+    // 21:34 in Tokyo, after astronomical dusk
+    assert_eq!(SolarEvent::AstronomicalDawn, event);
+    assert_near("2025-01-01T20:20:00Z", Some(time));
+
+    for coordinates in [
+        Coordinates {
+            latitude: 90.5,
+            longitude: 0.0,
+        },
+        Coordinates {
+            latitude: 0.0,
+            longitude: -180.5,
+        },
+        Coordinates {
+            latitude: f64::NAN,
+            longitude: 0.0,
+        },
+    ] {
+        assert!(coordinates.validate().is_err())
+    }
+}
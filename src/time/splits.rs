@@ -0,0 +1,887 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use super::Lap;
+use crate::{NeedleErr, NeedleError};
+use chrono::TimeDelta;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufWriter, Write},
+    ops::Range,
+    path::Path,
+    time::Duration,
+};
+
+/// Replacement of a byte range of a `.lss` file
+type Edit = (Range<usize>, String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitSegment {
+    pub name: String,
+    /// Run time at the end of this segment in the personal best
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub personal_best: Option<Duration>,
+    /// Fastest time this segment has been completed in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_segment: Option<Duration>,
+}
+
+/// Speedrun splits run by `OpMode::Splits`, read from and written to
+/// LiveSplit `.lss` files.
+///
+/// Only real-time comparisons are modeled. `SplitRun::save` patches the
+/// personal best, best segments and attempt count into an existing file,
+/// leaving segment history, attempt history, icons, game time, other
+/// comparisons and auto splitter settings as they are.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitRun {
+    #[serde(default)]
+    pub game: String,
+    #[serde(default)]
+    pub category: String,
+    /// Run time the timer starts at. Negative for a start delay.
+    #[serde(default, with = "lss_offset")]
+    pub offset: TimeDelta,
+    #[serde(default)]
+    pub attempts: u32,
+    pub segments: Vec<SplitSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitStatus {
+    /// Index of the running segment
+    pub index: usize,
+    pub count: usize,
+    pub name: String,
+    /// Time spent in the running segment
+    pub segment_time: Duration,
+    /// Run time compared to the personal best split of the running segment
+    pub delta: Option<TimeDelta>,
+    /// Running segment compared to its best segment
+    pub segment_delta: Option<TimeDelta>,
+    pub finished: bool,
+}
+
+/// Row of the split table, one per segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Split {
+    pub name: String,
+    /// Run time of the split, or the personal best for splits not reached yet
+    pub time: Option<Duration>,
+    /// Compared to the personal best. `None` for splits not reached yet
+    pub delta: Option<TimeDelta>,
+    /// Segment beat the best segment
+    pub gold: bool,
+}
+
+impl SplitRun {
+    const LSS_VERSION: &'static str = "1.7.0";
+    const PERSONAL_BEST: &'static str = "Personal Best";
+
+    pub fn from(path: &Path) -> NeedleErr<Self> {
+        if !path.exists() {
+            let path = path.to_string_lossy();
+
+            return Err(NeedleError::ConfigNonExistant(path.into()));
+        }
+
+        match fs::read_to_string(path) {
+            Ok(lss) => Self::parse(&lss),
+            Err(err) => Err(NeedleError::FailedToReadSplits(err.into())),
+        }
+    }
+
+    pub fn parse(lss: &str) -> NeedleErr<Self> {
+        let document = match roxmltree::Document::parse(lss) {
+            Ok(document) => Ok(document),
+            Err(err) => Err(NeedleError::InvalidSplits(err.to_string().into())),
+        }?;
+        let run = document.root_element();
+
+        if !run.has_tag_name("Run") {
+            return Err(NeedleError::InvalidSplits(
+                "root element must be Run".into(),
+            ));
+        }
+
+        let offset = match Self::text(Self::child(run, "Offset")) {
+            "" => Ok(TimeDelta::zero()),
+            offset => {
+                Self::parse_time(offset).ok_or_else(|| NeedleError::InvalidSplits(offset.into()))
+            }
+        }?;
+        let attempts = Self::text(Self::child(run, "AttemptCount"))
+            .parse()
+            .unwrap_or(0);
+        let segments = Self::child(run, "Segments")
+            .map(|segments| {
+                segments
+                    .children()
+                    .filter(|segment| segment.has_tag_name("Segment"))
+                    .map(|segment| {
+                        let personal_best = Self::child(segment, "SplitTimes").and_then(|times| {
+                            times.children().find(|time| {
+                                time.has_tag_name("SplitTime")
+                                    && time.attribute("name") == Some(Self::PERSONAL_BEST)
+                            })
+                        });
+
+                        Ok(SplitSegment {
+                            name: Self::text(Self::child(segment, "Name")).to_string(),
+                            personal_best: Self::real_time(personal_best)?,
+                            best_segment: Self::real_time(Self::child(segment, "BestSegmentTime"))?,
+                        })
+                    })
+                    .collect::<NeedleErr<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let split_run = Self {
+            game: Self::text(Self::child(run, "GameName")).to_string(),
+            category: Self::text(Self::child(run, "CategoryName")).to_string(),
+            offset,
+            attempts,
+            segments,
+        };
+
+        split_run.validate()?;
+
+        Ok(split_run)
+    }
+
+    pub fn validate(&self) -> NeedleErr<()> {
+        if self.segments.is_empty() {
+            Err(NeedleError::InvalidSplits("segments are empty".into()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes a new `.lss` file holding only what is modeled
+    pub fn to_lss(&self) -> String {
+        let time = |time: Option<Duration>| match time {
+            Some(time) => Self::real_time_to_str(&time),
+            None => String::new(),
+        };
+        let mut lines = vec![
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+            format!("<Run version=\"{}\">", Self::LSS_VERSION),
+            "  <GameIcon />".to_string(),
+            format!("  <GameName>{}</GameName>", escape(&self.game)),
+            format!("  <CategoryName>{}</CategoryName>", escape(&self.category)),
+            format!("  <Offset>{}</Offset>", Self::time_to_str(&self.offset)),
+            format!("  <AttemptCount>{}</AttemptCount>", self.attempts),
+            "  <AttemptHistory />".to_string(),
+            "  <Segments>".to_string(),
+        ];
+
+        for segment in &self.segments {
+            lines.extend([
+                "    <Segment>".to_string(),
+                format!("      <Name>{}</Name>", escape(&segment.name)),
+                "      <Icon />".to_string(),
+                "      <SplitTimes>".to_string(),
+                format!(
+                    "        <SplitTime name=\"{}\">{}</SplitTime>",
+                    Self::PERSONAL_BEST,
+                    time(segment.personal_best)
+                ),
+                "      </SplitTimes>".to_string(),
+                format!(
+                    "      <BestSegmentTime>{}</BestSegmentTime>",
+                    time(segment.best_segment)
+                ),
+                "      <SegmentHistory />".to_string(),
+                "    </Segment>".to_string(),
+            ]);
+        }
+
+        lines.extend([
+            "  </Segments>".to_string(),
+            "  <AutoSplitterSettings />".to_string(),
+            "</Run>".to_string(),
+        ]);
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Updates the personal best, best segments and attempt count of `lss`,
+    /// the file this run has been read from. Everything else is kept byte for
+    /// byte.
+    pub fn patch_lss(&self, lss: &str) -> NeedleErr<String> {
+        let document = match roxmltree::Document::parse(lss) {
+            Ok(document) => Ok(document),
+            Err(err) => Err(NeedleError::InvalidSplits(err.to_string().into())),
+        }?;
+        let run = document.root_element();
+        let segments = Self::child(run, "Segments")
+            .map(|segments| {
+                segments
+                    .children()
+                    .filter(|segment| segment.has_tag_name("Segment"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if !run.has_tag_name("Run") || segments.len() != self.segments.len() {
+            return Err(NeedleError::InvalidSplits(
+                "segments don't match the run".into(),
+            ));
+        }
+
+        let attempts = self.attempts.to_string();
+        let mut edits = vec![match Self::child(run, "AttemptCount") {
+            Some(count) => Self::set_content(lss, count, &attempts),
+            None => Self::append(
+                lss,
+                run,
+                &format!("<AttemptCount>{}</AttemptCount>", attempts),
+            ),
+        }];
+
+        for (node, segment) in segments.into_iter().zip(&self.segments) {
+            let split_times = Self::child(node, "SplitTimes");
+            let personal_best = split_times.and_then(|times| {
+                times.children().find(|time| {
+                    time.has_tag_name("SplitTime")
+                        && time.attribute("name") == Some(Self::PERSONAL_BEST)
+                })
+            });
+
+            match (split_times, personal_best, segment.personal_best) {
+                (_, Some(split_time), time) => {
+                    edits.extend(Self::set_real_time(lss, split_time, time))
+                }
+                (_, None, None) => (),
+                (Some(split_times), None, Some(time)) => edits.push(Self::append(
+                    lss,
+                    split_times,
+                    &format!(
+                        "<SplitTime name=\"{}\">{}</SplitTime>",
+                        Self::PERSONAL_BEST,
+                        Self::real_time_to_str(&time)
+                    ),
+                )),
+                (None, None, Some(time)) => edits.push(Self::append(
+                    lss,
+                    node,
+                    &format!(
+                        "<SplitTimes><SplitTime name=\"{}\">{}</SplitTime></SplitTimes>",
+                        Self::PERSONAL_BEST,
+                        Self::real_time_to_str(&time)
+                    ),
+                )),
+            }
+
+            match (Self::child(node, "BestSegmentTime"), segment.best_segment) {
+                (Some(best), time) => edits.extend(Self::set_real_time(lss, best, time)),
+                (None, None) => (),
+                (None, Some(time)) => edits.push(Self::append(
+                    lss,
+                    node,
+                    &format!(
+                        "<BestSegmentTime>{}</BestSegmentTime>",
+                        Self::real_time_to_str(&time)
+                    ),
+                )),
+            }
+        }
+
+        // Edits never overlap, applied from the end to keep ranges valid
+        edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+        let mut patched = lss.to_string();
+
+        for (range, text) in edits {
+            patched.replace_range(range, &text);
+        }
+
+        Ok(patched)
+    }
+
+    /// Patches the file at `path` if there is one, see `SplitRun::patch_lss`,
+    /// or writes a new one
+    pub fn save(&self, path: &Path) -> NeedleErr<()> {
+        let lss = if path.exists() {
+            match fs::read_to_string(path) {
+                Ok(lss) => self.patch_lss(&lss),
+                Err(err) => Err(NeedleError::FailedToReadSplits(err.into())),
+            }?
+        } else {
+            self.to_lss()
+        };
+        let file = match OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+        {
+            Ok(file) => Ok(file),
+            Err(err) => Err(NeedleError::FailedToWriteSplits(err.into())),
+        }?;
+        let mut buf_writer = BufWriter::new(file);
+
+        match write!(buf_writer, "{}", lss) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(NeedleError::FailedToWriteSplits(err.into())),
+        }
+    }
+
+    /// Run time after the timer has been running for `elapsed`
+    pub fn run_time(&self, elapsed: Duration) -> TimeDelta {
+        Self::saturating_sub(Self::delta(elapsed), -self.offset)
+    }
+
+    /// Resolves the running segment from the splits recorded as `laps`
+    pub fn status(&self, laps: &[Lap], elapsed: Duration) -> SplitStatus {
+        let count = self.segments.len();
+        let finished = laps.len() >= count;
+        let index = laps.len().min(count.saturating_sub(1));
+        let (run_time, previous) = if finished {
+            (
+                self.split_time(laps, index),
+                index
+                    .checked_sub(1)
+                    .map(|index| self.split_time(laps, index)),
+            )
+        } else {
+            (
+                self.run_time(elapsed),
+                laps.last().map(|lap| self.run_time(lap.total)),
+            )
+        };
+        let segment_time = Self::saturating_sub(run_time, previous.unwrap_or(TimeDelta::zero()))
+            .to_std()
+            .unwrap_or(Duration::new(0, 0));
+        let segment = self.segments.get(index);
+
+        SplitStatus {
+            index,
+            count,
+            name: segment
+                .map(|segment| segment.name.clone())
+                .unwrap_or_default(),
+            segment_time,
+            delta: segment
+                .and_then(|segment| segment.personal_best)
+                .map(|personal_best| Self::saturating_sub(run_time, Self::delta(personal_best))),
+            segment_delta: segment
+                .and_then(|segment| segment.best_segment)
+                .map(|best| Self::saturating_sub(Self::delta(segment_time), Self::delta(best))),
+            finished,
+        }
+    }
+
+    /// Split table of the current attempt
+    pub fn splits(&self, laps: &[Lap]) -> Vec<Split> {
+        let mut previous = TimeDelta::zero();
+
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| match laps.get(index) {
+                Some(_) => {
+                    let time = self.split_time(laps, index);
+                    let segment_time = Self::saturating_sub(time, previous);
+
+                    previous = time;
+
+                    Split {
+                        name: segment.name.clone(),
+                        time: time.to_std().ok(),
+                        delta: segment.personal_best.map(|personal_best| {
+                            Self::saturating_sub(time, Self::delta(personal_best))
+                        }),
+                        gold: segment
+                            .best_segment
+                            .is_some_and(|best| segment_time < Self::delta(best)),
+                    }
+                }
+                None => Split {
+                    name: segment.name.clone(),
+                    time: segment.personal_best,
+                    delta: None,
+                    gold: false,
+                },
+            })
+            .collect()
+    }
+
+    /// Records an attempt, updating best segments and replacing the personal
+    /// best if the attempt has been completed faster.
+    /// Returns `true` on a new personal best.
+    pub fn record_attempt(&mut self, laps: &[Lap]) -> bool {
+        let mut previous = TimeDelta::zero();
+
+        self.attempts += 1;
+
+        for index in 0..laps.len().min(self.segments.len()) {
+            let time = self.split_time(laps, index);
+            let segment_time = Self::saturating_sub(time, previous).to_std().ok();
+
+            previous = time;
+
+            if let Some(segment_time) = segment_time {
+                let best = &mut self.segments[index].best_segment;
+
+                if best.is_none_or(|best| segment_time < best) {
+                    *best = Some(segment_time);
+                }
+            }
+        }
+
+        if laps.len() < self.segments.len() {
+            return false;
+        }
+
+        let last = self.segments.len() - 1;
+        let final_time = self.split_time(laps, last).to_std().ok();
+        let personal_best = self.segments[last].personal_best;
+
+        match (final_time, personal_best) {
+            (Some(final_time), Some(personal_best)) if final_time >= personal_best => false,
+            (Some(_), _) => {
+                for index in 0..self.segments.len() {
+                    self.segments[index].personal_best = self.split_time(laps, index).to_std().ok();
+                }
+
+                true
+            }
+            (None, _) => false,
+        }
+    }
+
+    fn child<'a, 'input>(
+        node: roxmltree::Node<'a, 'input>,
+        name: &str,
+    ) -> Option<roxmltree::Node<'a, 'input>> {
+        node.children().find(|child| child.has_tag_name(name))
+    }
+
+    fn text<'a>(node: Option<roxmltree::Node<'a, '_>>) -> &'a str {
+        node.and_then(|node| node.text()).unwrap_or("").trim()
+    }
+
+    fn real_time(node: Option<roxmltree::Node<'_, '_>>) -> NeedleErr<Option<Duration>> {
+        match Self::text(node.and_then(|node| Self::child(node, "RealTime"))) {
+            "" => Ok(None),
+            time => Self::parse_time(time)
+                .and_then(|time| time.to_std().ok())
+                .map(Some)
+                .ok_or_else(|| NeedleError::InvalidSplits(time.into())),
+        }
+    }
+
+    fn split_time(&self, laps: &[Lap], index: usize) -> TimeDelta {
+        self.run_time(laps[index].total)
+    }
+
+    #[inline]
+    fn delta(duration: Duration) -> TimeDelta {
+        TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX)
+    }
+
+    /// `lhs - rhs` clamped to `TimeDelta::MIN..=TimeDelta::MAX`
+    fn saturating_sub(lhs: TimeDelta, rhs: TimeDelta) -> TimeDelta {
+        lhs.checked_sub(&rhs).unwrap_or(if rhs < TimeDelta::zero() {
+            TimeDelta::MAX
+        } else {
+            TimeDelta::MIN
+        })
+    }
+
+    fn real_time_to_str(time: &Duration) -> String {
+        format!(
+            "<RealTime>{}</RealTime>",
+            Self::time_to_str(&Self::delta(*time))
+        )
+    }
+
+    /// Sets the `RealTime` child of `node`, removing it for `None`
+    fn set_real_time(
+        lss: &str,
+        node: roxmltree::Node<'_, '_>,
+        time: Option<Duration>,
+    ) -> Option<Edit> {
+        match (Self::child(node, "RealTime"), time) {
+            (Some(real_time), Some(time)) => Some(Self::set_content(
+                lss,
+                real_time,
+                &Self::time_to_str(&Self::delta(time)),
+            )),
+            (Some(real_time), None) => Some((real_time.range(), String::new())),
+            (None, Some(time)) => Some(Self::append(lss, node, &Self::real_time_to_str(&time))),
+            (None, None) => None,
+        }
+    }
+
+    /// Replaces what is between the start and end tags of `node`
+    fn set_content(lss: &str, node: roxmltree::Node<'_, '_>, content: &str) -> Edit {
+        match Self::content_range(lss, node) {
+            Some(range) => (range, content.to_string()),
+            // `<Name attr="" />` becomes `<Name attr="">content</Name>`
+            None => {
+                let range = node.range();
+                let start_tag = lss[range.start..range.end - 2].trim_end();
+
+                (
+                    range,
+                    format!("{}>{}</{}>", start_tag, content, node.tag_name().name()),
+                )
+            }
+        }
+    }
+
+    /// Inserts `xml` after the last child of `node`
+    fn append(lss: &str, node: roxmltree::Node<'_, '_>, xml: &str) -> Edit {
+        match Self::content_range(lss, node) {
+            Some(range) => (range.end..range.end, xml.to_string()),
+            None => Self::set_content(lss, node, xml),
+        }
+    }
+
+    /// `None` for an empty-element tag
+    fn content_range(lss: &str, node: roxmltree::Node<'_, '_>) -> Option<Range<usize>> {
+        let range = node.range();
+        let element = &lss[range.clone()];
+
+        if element.ends_with("/>") {
+            return None;
+        }
+
+        // Attribute values may contain `>`, the start tag ends after them
+        let after_attributes = node
+            .attributes()
+            .map(|attribute| attribute.range().end)
+            .max()
+            .unwrap_or(range.start);
+        let start = after_attributes + lss[after_attributes..].find('>')? + 1;
+        let end = range.start + element.rfind("</")?;
+
+        Some(start..end)
+    }
+
+    /// Parses LiveSplit's `[-][d.]hh:mm:ss[.fffffff]` notation
+    fn parse_time(time: &str) -> Option<TimeDelta> {
+        let (negative, time) = match time.strip_prefix('-') {
+            Some(time) => (true, time),
+            None => (false, time),
+        };
+        let [hours, minutes, seconds] = time.split(':').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        let (days, hours) = match hours.split_once('.') {
+            Some((days, hours)) => (days.parse::<i64>().ok()?, hours),
+            None => (0, hours),
+        };
+        let (seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, "0"));
+
+        if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let nanos = format!("{:0<9}", fraction).get(..9)?.parse::<u32>().ok()?;
+        let secs = days
+            .checked_mul(86_400)?
+            .checked_add(hours.parse::<i64>().ok()?.checked_mul(3_600)?)?
+            .checked_add(minutes.parse::<i64>().ok()?.checked_mul(60)?)?
+            .checked_add(seconds.parse::<i64>().ok()?)?;
+        let delta = TimeDelta::new(secs, nanos)?;
+
+        Some(if negative { -delta } else { delta })
+    }
+
+    fn time_to_str(time: &TimeDelta) -> String {
+        let sign = if *time < TimeDelta::zero() { "-" } else { "" };
+        let time = time.abs();
+        let secs = time.num_seconds();
+        let days = secs / 86_400;
+        let ticks = time.subsec_nanos() / 100;
+        let clock = format!(
+            "{:02}:{:02}:{:02}.{:07}",
+            (secs / 3_600) % 24,
+            (secs / 60) % 60,
+            secs % 60,
+            ticks
+        );
+
+        if days > 0 {
+            format!("{}{}.{}", sign, days, clock)
+        } else {
+            format!("{}{}", sign, clock)
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `SplitRun::offset` in LiveSplit notation
+mod lss_offset {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(offset: &TimeDelta, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&SplitRun::time_to_str(offset))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TimeDelta, D::Error> {
+        let offset = String::deserialize(deserializer)?;
+
+        SplitRun::parse_time(&offset)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid offset ({})", offset)))
+    }
+}
+
+#[cfg(test)]
+const LSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Run version="1.7.0">
+  <GameIcon />
+  <GameName>Celeste</GameName>
+  <CategoryName>Any% &amp; Cassettes</CategoryName>
+  <Offset>-00:00:01.5000000</Offset>
+  <AttemptCount>12</AttemptCount>
+  <AttemptHistory>
+    <Attempt id="1" started="01/01/2025 00:00:00" isStartedSynced="True" ended="01/01/2025 00:30:00" isEndedSynced="True" />
+  </AttemptHistory>
+  <Segments>
+    <Segment>
+      <Name>Prologue</Name>
+      <Icon />
+      <SplitTimes>
+        <SplitTime name="Personal Best">
+          <RealTime>00:00:10.0000000</RealTime>
+          <GameTime>00:00:09.0000000</GameTime>
+        </SplitTime>
+      </SplitTimes>
+      <BestSegmentTime>
+        <RealTime>00:00:09.0000000</RealTime>
+      </BestSegmentTime>
+      <SegmentHistory />
+    </Segment>
+    <Segment>
+      <Name>Forsaken City</Name>
+      <Icon />
+      <SplitTimes>
+        <SplitTime name="Personal Best">
+          <RealTime>00:00:30.0000000</RealTime>
+        </SplitTime>
+      </SplitTimes>
+      <BestSegmentTime>
+        <RealTime>00:00:18.0000000</RealTime>
+      </BestSegmentTime>
+      <SegmentHistory />
+    </Segment>
+    <Segment>
+      <Name>Old Site</Name>
+      <Icon />
+      <SplitTimes>
+        <SplitTime name="Personal Best" />
+      </SplitTimes>
+      <BestSegmentTime />
+      <SegmentHistory />
+    </Segment>
+  </Segments>
+  <AutoSplitterSettings />
+</Run>
+"#;
+
+#[cfg(test)]
+fn laps(totals: &[u64]) -> Vec<Lap> {
+    totals
+        .iter()
+        .map(|total| Lap {
+            split: Duration::new(0, 0),
+            total: Duration::from_millis(*total),
+        })
+        .collect()
+}
+
+#[test]
+fn test_splits_0001() {
+    let run = SplitRun::parse(LSS).unwrap();
+
+    assert_eq!("Celeste", run.game);
+    assert_eq!("Any% & Cassettes", run.category);
+    assert_eq!(TimeDelta::milliseconds(-1_500), run.offset);
+    assert_eq!(12, run.attempts);
+    assert_eq!(
+        SplitSegment {
+            name: "Forsaken City".to_string(),
+            personal_best: Some(Duration::from_secs(30)),
+            best_segment: Some(Duration::from_secs(18)),
+        },
+        run.segments[1]
+    );
+    assert_eq!(None, run.segments[2].personal_best);
+    assert_eq!(run, SplitRun::parse(&run.to_lss()).unwrap());
+    assert!(SplitRun::parse("<Run><Segments /></Run>").is_err());
+    assert!(SplitRun::parse("<Splits />").is_err())
+}
+
+#[test]
+fn test_splits_0002() {
+    for (time, expected) in [
+        ("00:01:23.4560000", Some(TimeDelta::milliseconds(83_456))),
+        ("01:00:00", Some(TimeDelta::hours(1))),
+        ("-00:00:05.25", Some(TimeDelta::milliseconds(-5_250))),
+        (
+            "1.02:03:04.5000000",
+            Some(TimeDelta::seconds(93_784) + TimeDelta::milliseconds(500)),
+        ),
+        ("12:34", None),
+        ("99999999999999.00:00:00", None),
+        ("00:153722867280912930:00", None),
+        ("00:00:01.", None),
+        ("a:b:c", None),
+    ] {
+        let parsed = SplitRun::parse_time(time);
+
+        assert_eq!(expected, parsed, "{}", time);
+
+        if let Some(parsed) = parsed {
+            assert_eq!(
+                Some(parsed),
+                SplitRun::parse_time(&SplitRun::time_to_str(&parsed))
+            )
+        }
+    }
+}
+
+#[test]
+fn test_splits_0003() {
+    let run = SplitRun::parse(LSS).unwrap();
+    // Offset of -1.5s: splits at 9.5s and 26.5s of run time
+    let laps = laps(&[11_000, 28_000]);
+    let status = run.status(&laps[..1], Duration::from_millis(21_500));
+
+    assert_eq!(1, status.index);
+    assert_eq!("Forsaken City", status.name);
+    assert_eq!(Duration::from_millis(10_500), status.segment_time);
+    assert_eq!(Some(TimeDelta::milliseconds(-10_000)), status.delta);
+    assert_eq!(Some(TimeDelta::milliseconds(-7_500)), status.segment_delta);
+
+    let splits = run.splits(&laps);
+
+    assert_eq!(Some(Duration::from_millis(9_500)), splits[0].time);
+    assert_eq!(Some(TimeDelta::milliseconds(-500)), splits[0].delta);
+    assert!(!splits[0].gold);
+    // 17s against the best segment of 18s
+    assert!(splits[1].gold);
+    assert_eq!((None, None), (splits[2].time, splits[2].delta))
+}
+
+#[test]
+fn test_splits_0004() {
+    let mut run = SplitRun::parse(LSS).unwrap();
+
+    // Reset after two splits: only best segments are updated
+    assert!(!run.record_attempt(&laps(&[11_000, 28_000])));
+    assert_eq!(13, run.attempts);
+    assert_eq!(Some(Duration::from_secs(9)), run.segments[0].best_segment);
+    assert_eq!(Some(Duration::from_secs(17)), run.segments[1].best_segment);
+    assert_eq!(Some(Duration::from_secs(30)), run.segments[1].personal_best);
+
+    // Completed run
+    assert!(run.record_attempt(&laps(&[11_500, 31_500, 61_500])));
+    assert_eq!(
+        vec![
+            Some(Duration::from_secs(10)),
+            Some(Duration::from_secs(30)),
+            Some(Duration::from_secs(60)),
+        ],
+        run.segments
+            .iter()
+            .map(|segment| segment.personal_best)
+            .collect::<Vec<_>>()
+    );
+    assert!(
+        run.status(&laps(&[11_500, 31_500, 61_500]), Duration::from_secs(90))
+            .finished
+    );
+
+    // Slower run doesn't replace the personal best
+    assert!(!run.record_attempt(&laps(&[11_500, 31_500, 71_500])));
+    assert_eq!(Some(Duration::from_secs(60)), run.segments[2].personal_best);
+
+    let path = std::env::temp_dir().join("needle_test_splits_0004.lss");
+
+    run.save(&path).unwrap();
+
+    let loaded = SplitRun::from(&path);
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(run, loaded.unwrap())
+}
+
+#[test]
+fn test_splits_0005() {
+    let mut run = SplitRun::parse(LSS).unwrap();
+
+    assert!(run.record_attempt(&laps(&[11_000, 28_000, 58_000])));
+
+    let patched = run.patch_lss(LSS).unwrap();
+
+    assert_eq!(run, SplitRun::parse(&patched).unwrap());
+    // Only the personal best, best segments and attempt count change
+    assert_eq!(
+        LSS.replace("<AttemptCount>12", "<AttemptCount>13")
+            .replace(
+                "<RealTime>00:00:10.0000000</RealTime>\n          <GameTime>",
+                "<RealTime>00:00:09.5000000</RealTime>\n          <GameTime>"
+            )
+            .replace(
+                "<RealTime>00:00:30.0000000</RealTime>",
+                "<RealTime>00:00:26.5000000</RealTime>"
+            )
+            .replace(
+                "<RealTime>00:00:18.0000000</RealTime>",
+                "<RealTime>00:00:17.0000000</RealTime>"
+            )
+            .replace(
+                "<SplitTime name=\"Personal Best\" />",
+                "<SplitTime name=\"Personal Best\"><RealTime>00:00:56.5000000</RealTime></SplitTime>"
+            )
+            .replace(
+                "<BestSegmentTime />",
+                "<BestSegmentTime><RealTime>00:00:30.0000000</RealTime></BestSegmentTime>"
+            ),
+        patched
+    );
+
+    // Saving over the imported file keeps what isn't modeled
+    let path = std::env::temp_dir().join("needle_test_splits_0005.lss");
+
+    fs::write(&path, LSS).unwrap();
+    run.save(&path).unwrap();
+
+    let saved = fs::read_to_string(&path).unwrap();
+
+    fs::remove_file(&path).unwrap();
+    assert_eq!(patched, saved);
+
+    run.segments.pop();
+    assert!(run.patch_lss(LSS).is_err())
+}
+
+#[test]
+fn test_splits_0006() {
+    let mut run = SplitRun::parse(LSS).unwrap();
+
+    run.offset = TimeDelta::MAX;
+    assert_eq!(TimeDelta::MAX, run.run_time(Duration::from_secs(1)));
+    assert_eq!(TimeDelta::MAX, run.run_time(Duration::MAX));
+
+    run.offset = TimeDelta::MIN;
+    assert_eq!(TimeDelta::MIN, run.run_time(Duration::new(0, 0)));
+    assert_eq!(
+        1,
+        run.status(&laps(&[1_000]), Duration::from_secs(2))
+            .segment_time
+            .as_secs()
+    )
+}
@@ -0,0 +1,258 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+/// Frame rate of `TimeFormat::Timecode`.
+///
+/// 23.976 and 29.97 run 1000/1001 slower than their nominal rate. 23.976 is
+/// counted as non-drop-frame and drifts from real time, 29.97 drops frame
+/// numbers to stay in sync with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameRate {
+    #[serde(rename = "23.976")]
+    Fps23_976,
+    #[serde(rename = "24")]
+    Fps24,
+    #[serde(rename = "25")]
+    Fps25,
+    #[serde(rename = "29.97DF")]
+    Fps29_97DropFrame,
+    #[serde(rename = "30")]
+    Fps30,
+    #[serde(rename = "50")]
+    Fps50,
+    #[serde(rename = "60")]
+    Fps60,
+}
+
+/// SMPTE timecode, `HH:MM:SS:FF` or `HH:MM:SS;FF` for drop-frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timecode {
+    pub hours: u64,
+    pub minutes: u64,
+    pub seconds: u64,
+    pub frames: u64,
+    pub drop_frame: bool,
+}
+
+impl FrameRate {
+    /// Frames labelled per second
+    pub const fn nominal(&self) -> u64 {
+        match self {
+            Self::Fps23_976 | Self::Fps24 => 24,
+            Self::Fps25 => 25,
+            Self::Fps29_97DropFrame | Self::Fps30 => 30,
+            Self::Fps50 => 50,
+            Self::Fps60 => 60,
+        }
+    }
+
+    #[inline]
+    pub const fn is_drop_frame(&self) -> bool {
+        matches!(self, Self::Fps29_97DropFrame)
+    }
+
+    /// Frames shown per second as a fraction
+    const fn rate(&self) -> (u128, u128) {
+        match self {
+            Self::Fps23_976 => (24_000, 1_001),
+            Self::Fps29_97DropFrame => (30_000, 1_001),
+            rate => (rate.nominal() as u128, 1),
+        }
+    }
+
+    /// Time frame number `frames` starts at
+    pub fn frame_time(&self, frames: u64) -> Duration {
+        let (numerator, denominator) = self.rate();
+        let nanos = (frames as u128 * denominator * 1_000_000_000).div_ceil(numerator);
+
+        Duration::from_nanos(nanos as u64)
+    }
+
+    /// Frames fully shown within `duration`
+    pub fn frames(&self, duration: &Duration) -> u64 {
+        let (numerator, denominator) = self.rate();
+
+        (duration.as_nanos() * numerator / (denominator * 1_000_000_000)) as u64
+    }
+}
+
+impl Timecode {
+    /// Frame numbers 00 and 01 are skipped at the start of every minute
+    /// except every tenth minute
+    const DROPPED_FRAMES: u64 = 2;
+    const FRAMES_PER_MINUTE_DF: u64 = 60 * 30 - Self::DROPPED_FRAMES;
+    const FRAMES_PER_10_MINUTES_DF: u64 = 10 * 60 * 30 - 9 * Self::DROPPED_FRAMES;
+
+    pub fn from_duration(duration: &Duration, rate: FrameRate) -> Self {
+        Self::from_frames(rate.frames(duration), rate)
+    }
+
+    /// Timecode of a wall-clock time `since_midnight`. 29.97DF runs out of
+    /// labels about 0.09s before midnight, where the last frame is held.
+    pub fn time_of_day(since_midnight: &Duration, rate: FrameRate) -> Self {
+        let labels = if rate.is_drop_frame() {
+            Self::FRAMES_PER_10_MINUTES_DF * 6 * 24
+        } else {
+            rate.nominal() * 86_400
+        };
+
+        Self::from_frames(rate.frames(since_midnight).min(labels - 1), rate)
+    }
+
+    /// Labels the frame `frames` frames after 00:00:00:00
+    pub fn from_frames(frames: u64, rate: FrameRate) -> Self {
+        let label = if rate.is_drop_frame() {
+            let tens = frames / Self::FRAMES_PER_10_MINUTES_DF;
+            let rest = frames % Self::FRAMES_PER_10_MINUTES_DF;
+            let minutes = if rest < Self::DROPPED_FRAMES {
+                0
+            } else {
+                (rest - Self::DROPPED_FRAMES) / Self::FRAMES_PER_MINUTE_DF
+            };
+
+            frames + 9 * Self::DROPPED_FRAMES * tens + Self::DROPPED_FRAMES * minutes
+        } else {
+            frames
+        };
+        let nominal = rate.nominal();
+
+        Self {
+            hours: label / (nominal * 3_600),
+            minutes: (label / (nominal * 60)) % 60,
+            seconds: (label / nominal) % 60,
+            frames: label % nominal,
+            drop_frame: rate.is_drop_frame(),
+        }
+    }
+
+    /// Inverse of `Timecode::from_frames`
+    pub fn to_frames(&self, rate: FrameRate) -> u64 {
+        let nominal = rate.nominal();
+        let total_minutes = self.hours * 60 + self.minutes;
+        let label = (total_minutes * 60 + self.seconds) * nominal + self.frames;
+
+        if rate.is_drop_frame() {
+            label - Self::DROPPED_FRAMES * (total_minutes - total_minutes / 10)
+        } else {
+            label
+        }
+    }
+}
+
+impl Display for FrameRate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rate = match self {
+            Self::Fps23_976 => "23.976",
+            Self::Fps24 => "24",
+            Self::Fps25 => "25",
+            Self::Fps29_97DropFrame => "29.97DF",
+            Self::Fps30 => "30",
+            Self::Fps50 => "50",
+            Self::Fps60 => "60",
+        };
+
+        write!(f, "{}", rate)
+    }
+}
+
+impl Display for Timecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            if self.drop_frame { ";" } else { ":" },
+            self.frames
+        )
+    }
+}
+
+#[test]
+fn test_timecode_0001() {
+    // Reference drop-frame timecodes of 29.97 fps
+    for (frames, expected) in [
+        (0, "00:00:00;00"),
+        (29, "00:00:00;29"),
+        (1_799, "00:00:59;29"),
+        (1_800, "00:01:00;02"),
+        (1_801, "00:01:00;03"),
+        (3_597, "00:01:59;29"),
+        (3_598, "00:02:00;02"),
+        (17_981, "00:09:59;29"),
+        (17_982, "00:10:00;00"),
+        (17_983, "00:10:00;01"),
+        (19_781, "00:10:59;29"),
+        (19_782, "00:11:00;02"),
+        (107_892, "01:00:00;00"),
+        (2_589_407, "23:59:59;29"),
+    ] {
+        let timecode = Timecode::from_frames(frames, FrameRate::Fps29_97DropFrame);
+
+        assert_eq!(expected, timecode.to_string(), "frame {}", frames);
+        assert_eq!(frames, timecode.to_frames(FrameRate::Fps29_97DropFrame))
+    }
+}
+
+#[test]
+fn test_timecode_0002() {
+    let hour = Duration::from_secs(3_600);
+
+    for (rate, expected) in [
+        // Drifts 3.6s behind real time every hour
+        (FrameRate::Fps23_976, "00:59:56:09"),
+        (FrameRate::Fps24, "01:00:00:00"),
+        (FrameRate::Fps25, "01:00:00:00"),
+        // Drop-frame keeps up with real time
+        (FrameRate::Fps29_97DropFrame, "01:00:00;00"),
+        (FrameRate::Fps30, "01:00:00:00"),
+        (FrameRate::Fps50, "01:00:00:00"),
+        (FrameRate::Fps60, "01:00:00:00"),
+    ] {
+        assert_eq!(
+            expected,
+            Timecode::from_duration(&hour, rate).to_string(),
+            "{}",
+            rate
+        )
+    }
+
+    assert_eq!(
+        "00:00:01:12",
+        Timecode::from_duration(&Duration::from_millis(1_500), FrameRate::Fps25).to_string()
+    );
+    assert_eq!(
+        "00:00:00:59",
+        Timecode::from_duration(&Duration::from_nanos(999_999_999), FrameRate::Fps60).to_string()
+    );
+    // 1001 frames of 23.976 last just over 41.75s
+    assert_eq!(
+        "00:00:41:16",
+        Timecode::from_duration(&Duration::from_millis(41_750), FrameRate::Fps23_976).to_string()
+    );
+    assert_eq!(
+        "00:00:41:17",
+        Timecode::from_duration(&Duration::from_millis(41_751), FrameRate::Fps23_976).to_string()
+    )
+}
+
+#[test]
+fn test_timecode_0003() {
+    let before_midnight = Duration::from_millis(86_399_950);
+
+    assert_eq!(
+        "23:59:59;29",
+        Timecode::time_of_day(&before_midnight, FrameRate::Fps29_97DropFrame).to_string()
+    );
+    assert_eq!(
+        "23:59:59:28",
+        Timecode::time_of_day(&before_midnight, FrameRate::Fps30).to_string()
+    )
+}
@@ -0,0 +1,218 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Wording table of `Representation::Words`.
+///
+/// A new locale is added by implementing this trait and registering it in
+/// `Locale::words`.
+pub trait TimeWords {
+    /// `hour` is 0-23 and `minute` 0-59, already rounded
+    fn words(&self, hour: u32, minute: u32) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    English,
+    Japanese,
+}
+
+/// Precision of the time put into words
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Fuzziness {
+    #[default]
+    Exact,
+    /// Nearest 5 minutes
+    FiveMinutes,
+    /// Nearest 15 minutes
+    QuarterHour,
+}
+
+/// e.g. "quarter past ten"
+pub struct English;
+
+/// e.g. "十時十五分"
+pub struct Japanese;
+
+impl Locale {
+    pub fn words(&self) -> &'static dyn TimeWords {
+        match self {
+            Self::English => &English,
+            Self::Japanese => &Japanese,
+        }
+    }
+}
+
+impl Fuzziness {
+    /// Rounds to the nearest step, rolling over into the next hour and day
+    pub fn round(&self, hour: u32, minute: u32, second: u32) -> (u32, u32) {
+        let step = match self {
+            Self::Exact => return (hour, minute),
+            Self::FiveMinutes => 5 * 60,
+            Self::QuarterHour => 15 * 60,
+        };
+        let seconds = (hour * 60 + minute) * 60 + second;
+        let minutes = ((seconds + step / 2) / step * step / 60) % (24 * 60);
+
+        (minutes / 60, minutes % 60)
+    }
+}
+
+impl English {
+    const NUMBERS: [&'static str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+
+    fn number(value: u32) -> String {
+        match value {
+            0..=19 => Self::NUMBERS[value as usize].to_string(),
+            20 => "twenty".to_string(),
+            _ => format!("twenty-{}", Self::NUMBERS[value as usize - 20]),
+        }
+    }
+
+    fn hour(hour: u32) -> String {
+        match hour % 12 {
+            0 => Self::number(12),
+            hour => Self::number(hour),
+        }
+    }
+
+    fn minutes(minutes: u32) -> String {
+        match minutes {
+            15 => "quarter".to_string(),
+            30 => "half".to_string(),
+            1 => "one minute".to_string(),
+            minutes if minutes % 5 == 0 => Self::number(minutes),
+            minutes => format!("{} minutes", Self::number(minutes)),
+        }
+    }
+}
+
+impl TimeWords for English {
+    fn words(&self, hour: u32, minute: u32) -> String {
+        match minute {
+            0 => format!("{} o'clock", Self::hour(hour)),
+            1..=30 => format!("{} past {}", Self::minutes(minute), Self::hour(hour)),
+            _ => format!("{} to {}", Self::minutes(60 - minute), Self::hour(hour + 1)),
+        }
+    }
+}
+
+impl Japanese {
+    const DIGITS: [&'static str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+    fn number(value: u32) -> String {
+        let ones = match value % 10 {
+            0 => "",
+            ones => Self::DIGITS[ones as usize],
+        };
+
+        match value / 10 {
+            0 => Self::DIGITS[value as usize].to_string(),
+            1 => format!("十{}", ones),
+            tens => format!("{}十{}", Self::DIGITS[tens as usize], ones),
+        }
+    }
+}
+
+impl TimeWords for Japanese {
+    fn words(&self, hour: u32, minute: u32) -> String {
+        let hour = match hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+
+        match minute {
+            0 => format!("{}時", Self::number(hour)),
+            30 => format!("{}時半", Self::number(hour)),
+            minute => format!("{}時{}分", Self::number(hour), Self::number(minute)),
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::English => write!(f, "English"),
+            Self::Japanese => write!(f, "Japanese"),
+        }
+    }
+}
+
+impl Display for Fuzziness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact => write!(f, "Exact"),
+            Self::FiveMinutes => write!(f, "FiveMinutes"),
+            Self::QuarterHour => write!(f, "QuarterHour"),
+        }
+    }
+}
+
+#[test]
+fn test_words_0001() {
+    for ((hour, minute), expected) in [
+        ((10, 0), "ten o'clock"),
+        ((0, 0), "twelve o'clock"),
+        ((10, 1), "one minute past ten"),
+        ((10, 5), "five past ten"),
+        ((10, 7), "seven minutes past ten"),
+        ((10, 15), "quarter past ten"),
+        ((22, 25), "twenty-five past ten"),
+        ((10, 30), "half past ten"),
+        ((10, 38), "twenty-two minutes to eleven"),
+        ((10, 45), "quarter to eleven"),
+        ((11, 59), "one minute to twelve"),
+        ((23, 50), "ten to twelve"),
+    ] {
+        assert_eq!(expected, English.words(hour, minute))
+    }
+}
+
+#[test]
+fn test_words_0002() {
+    for ((hour, minute), expected) in [
+        ((10, 0), "十時"),
+        ((0, 0), "十二時"),
+        ((10, 15), "十時十五分"),
+        ((21, 30), "九時半"),
+        ((1, 1), "一時一分"),
+        ((11, 20), "十一時二十分"),
+        ((7, 59), "七時五十九分"),
+    ] {
+        assert_eq!(expected, Japanese.words(hour, minute))
+    }
+}
+
+#[test]
+fn test_words_0003() {
+    assert_eq!((10, 7), Fuzziness::Exact.round(10, 7, 40));
+    assert_eq!((10, 5), Fuzziness::FiveMinutes.round(10, 7, 29));
+    assert_eq!((10, 10), Fuzziness::FiveMinutes.round(10, 7, 30));
+    assert_eq!((10, 15), Fuzziness::QuarterHour.round(10, 22, 29));
+    assert_eq!((11, 0), Fuzziness::QuarterHour.round(10, 53, 0));
+    assert_eq!((0, 0), Fuzziness::FiveMinutes.round(23, 58, 0))
+}
@@ -0,0 +1,277 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use crate::{NeedleErr, NeedleError};
+use chrono::{DateTime, FixedOffset, Local, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Timezone used to render `OpMode::Clock`.
+///
+/// Accepted notations:
+///  - `Local` : Timezone of the system
+///  - IANA timezone name (e.g. `Asia/Tokyo`, `UTC`), resolved from the
+///    bundled tz database so no system tz data is required
+///  - Fixed UTC offset (e.g. `+09:00`, `-0530`, `UTC+9`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Zone {
+    #[default]
+    Local,
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    const MAX_OFFSET_SECS: i32 = 24 * 60 * 60;
+
+    pub fn parse(name: &str) -> NeedleErr<Self> {
+        let name = name.trim();
+        let invalid = |reason: &str| -> NeedleError {
+            NeedleError::InvalidTimeZone(format!("\"{}\": {}", name, reason).into())
+        };
+
+        if name.is_empty() {
+            return Err(invalid("timezone is empty"));
+        }
+
+        if name.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+
+        let offset = name
+            .strip_prefix("UTC")
+            .or_else(|| name.strip_prefix("GMT"))
+            .filter(|offset| !offset.is_empty())
+            .unwrap_or(name);
+
+        if offset.starts_with(['+', '-']) {
+            return Self::parse_offset(offset)
+                .map(Self::Fixed)
+                .ok_or_else(|| invalid("invalid UTC offset"));
+        }
+
+        name.parse::<Tz>()
+            .map(Self::Named)
+            .map_err(|_| invalid("unknown timezone name"))
+    }
+
+    /// Parses a wall-clock instant such as `2026-12-01T09:00`.
+    ///
+    /// RFC 3339 timestamps carry their own offset, otherwise the time is
+    /// interpreted in this zone. A time repeated by a DST transition resolves
+    /// to its earlier occurrence, a skipped time is rejected.
+    pub fn parse_datetime(&self, text: &str) -> NeedleErr<DateTime<Utc>> {
+        const FORMATS: [&str; 4] = [
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M",
+        ];
+        let text = text.trim();
+        let invalid = |reason: &str| -> NeedleError {
+            NeedleError::InvalidTargetTime(format!("\"{}\": {}", text, reason).into())
+        };
+
+        if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+            return Ok(time.with_timezone(&Utc));
+        }
+
+        let naive = FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+            .ok_or_else(|| invalid("expected \"YYYY-MM-DDTHH:MM[:SS]\""))?;
+
+        self.from_local(&naive)
+            .earliest()
+            .ok_or_else(|| invalid(&format!("time doesn't exist in {}", self)))
+    }
+
+    /// Resolves a wall-clock time of this zone, which may be skipped or
+    /// repeated by a DST transition
+    pub fn from_local(&self, naive: &NaiveDateTime) -> LocalResult<DateTime<Utc>> {
+        match self {
+            Self::Local => Self::resolve(Local.from_local_datetime(naive)),
+            Self::Named(tz) => Self::resolve(tz.from_local_datetime(naive)),
+            Self::Fixed(offset) => Self::resolve(offset.from_local_datetime(naive)),
+        }
+    }
+
+    /// Wall-clock time of this zone at `time`
+    pub fn to_local(&self, time: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => time.with_timezone(&Local).naive_local(),
+            Self::Named(tz) => time.with_timezone(tz).naive_local(),
+            Self::Fixed(offset) => time.with_timezone(offset).naive_local(),
+        }
+    }
+
+    fn resolve<Tz: TimeZone>(time: LocalResult<DateTime<Tz>>) -> LocalResult<DateTime<Utc>> {
+        time.map(|time| time.with_timezone(&Utc))
+    }
+
+    fn parse_offset(offset: &str) -> Option<FixedOffset> {
+        // Split at byte indexes below
+        if !offset.is_ascii() {
+            return None;
+        }
+
+        let (sign, offset) = match offset.split_at(1) {
+            ("+", offset) => (1, offset),
+            ("-", offset) => (-1, offset),
+            _ => return None,
+        };
+        let (hour, minute) = match offset.split_once(':') {
+            Some((hour, minute)) => (hour, minute),
+            None if offset.len() > 2 => offset.split_at(offset.len() - 2),
+            None => (offset, "0"),
+        };
+
+        if hour.is_empty() || !(hour.len() <= 2 && minute.len() <= 2) {
+            return None;
+        }
+        // `parse` would take another sign
+        if !(hour.bytes().all(|b| b.is_ascii_digit()) && minute.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+
+        let hour = hour.parse::<i32>().ok()?;
+        let minute = minute.parse::<i32>().ok()?;
+        let secs = (hour * 60 + minute) * 60;
+
+        if minute >= 60 || secs >= Self::MAX_OFFSET_SECS {
+            return None;
+        }
+
+        FixedOffset::east_opt(sign * secs)
+    }
+}
+
+impl TryFrom<String> for Zone {
+    type Error = NeedleError;
+
+    fn try_from(name: String) -> NeedleErr<Self> {
+        Self::parse(&name)
+    }
+}
+
+impl From<Zone> for String {
+    fn from(zone: Zone) -> Self {
+        zone.to_string()
+    }
+}
+
+impl Display for Zone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "Local"),
+            Self::Named(tz) => write!(f, "{}", tz.name()),
+            Self::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+/// Labelled timezone rendered by `OpMode::WorldClock`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct WorldClock {
+    pub label: String,
+    pub timezone: Zone,
+}
+
+impl Display for WorldClock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ label = {}, timezone = \"{}\" }}",
+            toml::Value::String(self.label.clone()),
+            self.timezone
+        )
+    }
+}
+
+#[test]
+fn test_zone_0001() {
+    assert_eq!(Zone::Local, Zone::parse("Local").unwrap());
+    assert_eq!(
+        Zone::Named(chrono_tz::Asia::Tokyo),
+        Zone::parse("Asia/Tokyo").unwrap()
+    );
+    assert_eq!(Zone::Named(chrono_tz::UTC), Zone::parse("UTC").unwrap());
+    assert_eq!(
+        Zone::Fixed(FixedOffset::east_opt(9 * 3_600).unwrap()),
+        Zone::parse("UTC+9").unwrap()
+    );
+    assert_eq!(
+        Zone::Fixed(FixedOffset::west_opt(5 * 3_600 + 30 * 60).unwrap()),
+        Zone::parse("-0530").unwrap()
+    );
+    assert_eq!(
+        Zone::Fixed(FixedOffset::east_opt(5 * 3_600 + 45 * 60).unwrap()),
+        Zone::parse("+05:45").unwrap()
+    )
+}
+
+#[test]
+fn test_zone_0002() {
+    assert!(Zone::parse("").is_err());
+    assert!(Zone::parse("Mars/Olympus_Mons").is_err());
+    assert!(Zone::parse("+25:00").is_err());
+    assert!(Zone::parse("+09:75").is_err());
+    assert!(Zone::parse("UTC+").is_err());
+    assert!(Zone::parse("+123:00").is_err())
+}
+
+#[test]
+fn test_zone_0003() {
+    for name in ["Local", "Europe/London", "+09:00", "-05:30"] {
+        assert_eq!(name, Zone::parse(name).unwrap().to_string())
+    }
+}
+
+#[test]
+fn test_zone_0004() {
+    let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+    let expected = Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap();
+
+    assert_eq!(expected, tokyo.parse_datetime("2026-12-01T09:00").unwrap());
+    assert_eq!(
+        expected,
+        tokyo.parse_datetime("2026-12-01 09:00:00").unwrap()
+    );
+    assert_eq!(
+        expected,
+        Zone::Local
+            .parse_datetime("2026-11-30T15:00:00-09:00")
+            .unwrap()
+    );
+    assert!(tokyo.parse_datetime("2026-12-01").is_err());
+    assert!(tokyo.parse_datetime("2026-13-01T09:00").is_err())
+}
+
+#[test]
+fn test_zone_0005() {
+    let new_york = Zone::parse("America/New_York").unwrap();
+
+    // Skipped by the transition to daylight saving time
+    assert!(new_york.parse_datetime("2026-03-08T02:30").is_err());
+    // Repeated by the transition to standard time
+    assert_eq!(
+        Utc.with_ymd_and_hms(2026, 11, 1, 5, 30, 0).unwrap(),
+        new_york.parse_datetime("2026-11-01T01:30").unwrap()
+    )
+}
+
+#[test]
+fn test_zone_0006() {
+    for name in [
+        "+aéb", "-é", "UTC+0é", "é", "+09:3é", "+-5", "UTC+-5", "+05:-30", "-+5",
+    ] {
+        assert!(
+            matches!(Zone::parse(name), Err(NeedleError::InvalidTimeZone(_))),
+            "{}",
+            name
+        )
+    }
+}